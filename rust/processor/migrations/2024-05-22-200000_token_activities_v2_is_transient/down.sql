@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE IF EXISTS token_activities_v2 DROP COLUMN is_transient;
@@ -3,7 +3,8 @@
 
 use crate::{
     gap_detector::DEFAULT_GAP_DETECTION_BATCH_SIZE, processors::ProcessorConfig,
-    transaction_filter::TransactionFilter, worker::Worker,
+    transaction_filter::TransactionFilter, utils::database::DEFAULT_DB_WRITE_RETRIES,
+    worker::Worker,
 };
 use ahash::AHashMap;
 use anyhow::{Context, Result};
@@ -49,6 +50,14 @@ pub struct IndexerGrpcProcessorConfig {
 
     #[serde(default)]
     pub transaction_filter: TransactionFilter,
+    // Parse transactions without writing anything to the DB, only log what would have been written
+    #[serde(default)]
+    pub dry_run: bool,
+    // Number of times a write is retried on serialization failures and deadlocks
+    #[serde(default = "IndexerGrpcProcessorConfig::default_db_write_retries")]
+    pub db_write_retries: u32,
+    // Chain id the stream must be for, e.g. 1 for mainnet. Refuses to start on any other chain
+    pub expected_chain_id: Option<u64>,
 }
 
 impl IndexerGrpcProcessorConfig {
@@ -64,6 +73,10 @@ impl IndexerGrpcProcessorConfig {
         QUERY_DEFAULT_RETRY_DELAY_MS
     }
 
+    pub const fn default_db_write_retries() -> u32 {
+        DEFAULT_DB_WRITE_RETRIES
+    }
+
     /// Make the default very large on purpose so that by default it's not chunked
     /// This prevents any unexpected changes in behavior
     pub const fn default_pb_channel_txn_chunk_size() -> usize {
@@ -95,11 +108,13 @@ impl RunnableConfig for IndexerGrpcProcessorConfig {
             self.enable_verbose_logging,
             self.transaction_filter.clone(),
             self.grpc_response_item_timeout_in_secs,
+            self.dry_run,
+            self.db_write_retries,
+            self.expected_chain_id,
         )
         .await
         .context("Failed to build worker")?;
-        worker.run().await;
-        Ok(())
+        worker.run().await
     }
 
     fn get_server_name(&self) -> String {
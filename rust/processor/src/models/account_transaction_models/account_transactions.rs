@@ -36,7 +36,8 @@ impl AccountTransaction {
     /// a user account, an object, or a resource account.
     /// We will consider all transactions that modify a resource or event associated with a particular account.
     /// We will do 1 level of redirection for now (e.g. if it's an object, we will record the owner as account address).
-    /// We will also consider transactions that the account signed or is part of a multi sig / multi agent.
+    /// We will also consider transactions that the account signed or is part of a multi sig / multi agent,
+    /// including the fee payer of fee payer transactions.
     /// TODO: recursively find the parent account of an object
     /// TODO: include table items in the detection path
     pub fn from_transaction(transaction: &Transaction) -> AHashMap<AccountTransactionPK, Self> {
@@ -151,3 +152,53 @@ impl AccountTransaction {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        account_signature::Signature as AccountSignatureEnum,
+        signature::Signature as SignatureEnum, AccountSignature, Ed25519Signature,
+        FeePayerSignature, Signature, UserTransaction, UserTransactionRequest,
+    };
+
+    fn ed25519_account_signature() -> AccountSignature {
+        AccountSignature {
+            signature: Some(AccountSignatureEnum::Ed25519(Ed25519Signature {
+                public_key: vec![1; 32],
+                signature: vec![1; 64],
+            })),
+            ..AccountSignature::default()
+        }
+    }
+
+    #[test]
+    fn test_fee_payer_is_recorded() {
+        let transaction = Transaction {
+            version: 5,
+            info: Some(Default::default()),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "0x1".to_string(),
+                    signature: Some(Signature {
+                        signature: Some(SignatureEnum::FeePayer(FeePayerSignature {
+                            sender: Some(ed25519_account_signature()),
+                            fee_payer_address: "0x3".to_string(),
+                            fee_payer_signer: Some(ed25519_account_signature()),
+                            ..FeePayerSignature::default()
+                        })),
+                        ..Signature::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                ..UserTransaction::default()
+            })),
+            ..Transaction::default()
+        };
+
+        let account_transactions = AccountTransaction::from_transaction(&transaction);
+        assert_eq!(account_transactions.len(), 2);
+        assert!(account_transactions.contains_key(&(standardize_address("0x1"), 5)));
+        assert!(account_transactions.contains_key(&(standardize_address("0x3"), 5)));
+    }
+}
@@ -25,6 +25,8 @@ pub struct Event {
     pub account_address: String,
     pub transaction_version: i64,
     pub transaction_block_height: i64,
+    // Null for events stored before the epoch was tracked
+    pub epoch: Option<i64>,
     pub type_: String,
     pub data: serde_json::Value,
     pub event_index: i64,
@@ -53,6 +55,7 @@ impl Event {
         event: &EventPB,
         transaction_version: i64,
         transaction_block_height: i64,
+        epoch: i64,
         event_index: i64,
         request: &Option<UserTransactionRequest>,
         inserted_at: &Option<Timestamp>,
@@ -90,6 +93,7 @@ impl Event {
                 sequence_number: event.sequence_number as i64,
                 transaction_version,
                 transaction_block_height,
+                epoch: Some(epoch),
                 type_: t.to_string(),
                 data: serde_json::from_str(event.data.as_str()).unwrap(),
                 event_index,
@@ -111,6 +115,7 @@ impl Event {
                 sequence_number: event.sequence_number as i64,
                 transaction_version,
                 transaction_block_height,
+                epoch: Some(epoch),
                 type_: t.to_string(),
                 data: serde_json::from_str(event.data.as_str()).unwrap(),
                 event_index,
@@ -118,8 +123,8 @@ impl Event {
                 from: "".to_string(),
                 entry_function_payload: serde_json::Value::Null,
                 entry_function_id_str: "".to_string(),
-                module_address: t.split("::").next().unwrap().to_string(),
-                module_name: t.split("::").nth(1).unwrap().to_string(),
+                module_address: t.split("::").next().unwrap_or("").to_string(),
+                module_name: t.split("::").nth(1).unwrap_or("").to_string(),
                 event_name: event_name.to_string(),
                 inserted_at: timestamp_to_naive(inserted_at),
             }
@@ -130,6 +135,7 @@ impl Event {
         events: &[EventPB],
         transaction_version: i64,
         transaction_block_height: i64,
+        epoch: i64,
         request: &Option<UserTransactionRequest>,
         inserted_at: &Option<Timestamp>,
     ) -> Vec<Self> {
@@ -141,6 +147,7 @@ impl Event {
                     event,
                     transaction_version,
                     transaction_block_height,
+                    epoch,
                     index as i64,
                     request,
                     inserted_at,
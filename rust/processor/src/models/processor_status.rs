@@ -38,3 +38,35 @@ impl ProcessorStatusQuery {
             .optional()
     }
 }
+
+/// Seconds between `now` and the last processed transaction, floored at 0
+pub fn processor_lag_in_secs(
+    now: chrono::NaiveDateTime,
+    last_transaction_timestamp: Option<chrono::NaiveDateTime>,
+) -> Option<f64> {
+    last_transaction_timestamp
+        .map(|timestamp| ((now - timestamp).num_milliseconds() as f64 / 1000.0).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    #[test]
+    fn test_processor_lag_in_secs() {
+        let now = NaiveDateTime::from_timestamp_opt(1_700_000_100, 500_000_000).unwrap();
+        let last_transaction_timestamp = NaiveDateTime::from_timestamp_opt(1_700_000_000, 0);
+
+        assert_eq!(
+            processor_lag_in_secs(now, last_transaction_timestamp),
+            Some(100.5)
+        );
+        assert_eq!(processor_lag_in_secs(now, None), None);
+        // Clock skew shouldn't report a negative lag
+        assert_eq!(
+            processor_lag_in_secs(last_transaction_timestamp.unwrap(), Some(now)),
+            Some(0.0)
+        );
+    }
+}
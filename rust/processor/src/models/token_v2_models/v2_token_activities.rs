@@ -5,14 +5,14 @@
 #![allow(clippy::extra_unused_lifetimes)]
 #![allow(clippy::unused_unit)]
 
-use super::v2_token_utils::{TokenStandard, V2TokenEvent};
+use super::v2_token_utils::{TokenStandard, TokenV2Minted, TokenV2Transferred, V2TokenEvent};
 use crate::{
     models::{
         object_models::v2_object_utils::ObjectAggregatedDataMapping,
         token_models::token_utils::{TokenDataIdType, TokenEvent},
     },
     schema::token_activities_v2,
-    utils::util::standardize_address,
+    utils::{counters::TOKEN_V2_MISSING_METADATA_COUNT, util::standardize_address},
 };
 use aptos_protos::transaction::v1::Event;
 use bigdecimal::{BigDecimal, One, Zero};
@@ -38,6 +38,8 @@ pub struct TokenActivityV2 {
     pub token_standard: String,
     pub is_fungible_v2: Option<bool>,
     pub transaction_timestamp: chrono::NaiveDateTime,
+    /// Whether the token was minted and burned within the same transaction (v2 only)
+    pub is_transient: Option<bool>,
 }
 
 /// A simplified TokenActivity (excluded common fields) to reduce code duplication
@@ -67,6 +69,8 @@ impl TokenActivityV2 {
         event_index: i64,
         entry_function_id_str: &Option<String>,
         token_v2_metadata: &ObjectAggregatedDataMapping,
+        tokens_minted: &TokenV2Minted,
+        tokens_transferred: &TokenV2Transferred,
     ) -> anyhow::Result<Option<Self>> {
         let event_type = event.type_str.clone();
         if let Some(token_event) =
@@ -128,8 +132,16 @@ impl TokenActivityV2 {
                         event_type: "0x4::collection::BurnEvent".to_string(),
                     },
                     V2TokenEvent::TransferEvent(inner) => TokenActivityHelperV2 {
-                        from_address: Some(inner.get_from_address()),
-                        to_address: Some(inner.get_to_address()),
+                        from_address: inner.try_get_from_address(),
+                        // The object metadata is the state at the end of the transaction, so its
+                        // owner is only the receiver of the last transfer of the object
+                        to_address: inner.try_get_to_address().or_else(|| {
+                            tokens_transferred
+                                .get(&token_data_id)
+                                .and_then(|transfers| transfers.last())
+                                .filter(|(index, _)| *index == event_index)
+                                .map(|_| object_core.get_owner_address())
+                        }),
                         token_amount: BigDecimal::one(),
                         before_value: None,
                         after_value: None,
@@ -150,19 +162,46 @@ impl TokenActivityV2 {
                     after_value: token_activity_helper.after_value,
                     entry_function_id_str: entry_function_id_str.clone(),
                     token_standard: TokenStandard::V2.to_string(),
-                    is_fungible_v2: None,
+                    // Only fungible tokens carry fungible asset metadata on the token object
+                    is_fungible_v2: Some(metadata.fungible_asset_metadata.is_some()),
                     transaction_timestamp: txn_timestamp,
+                    is_transient: Some(false),
                 }));
             } else {
+                TOKEN_V2_MISSING_METADATA_COUNT
+                    .with_label_values(&[event_type.as_str()])
+                    .inc();
+                tracing::debug!(
+                    transaction_version = txn_version,
+                    token_data_id = token_data_id,
+                    event_type = event_type,
+                    "Token object metadata missing for token v2 activity"
+                );
                 // If the object metadata isn't found in the transaction, then the token was burnt.
+                // If it was also minted in this transaction it never outlived it, which we flag
+                // to tell it apart from a burn of a token that existed before.
+                let is_transient = tokens_minted.contains(&token_data_id);
 
                 // the new burn event has owner address now!
                 let owner_address = if let V2TokenEvent::Burn(inner) = token_event {
                     inner.get_previous_owner_address()
                 } else {
-                    // To handle a case with the old burn events, when a token is minted and burnt in the same transaction
                     None
                 };
+                // Old burn events don't have it. A token minted in this transaction is owned by
+                // whoever it was last transferred to before the burn. If it was never transferred
+                // it's still owned by its creator, which the mint and burn events don't carry.
+                let owner_address = owner_address.or_else(|| {
+                    if !is_transient {
+                        return None;
+                    }
+                    tokens_transferred
+                        .get(&token_data_id)?
+                        .iter()
+                        .filter(|(index, _)| *index < event_index)
+                        .filter_map(|(_, transfer)| transfer.try_get_to_address())
+                        .last()
+                });
 
                 return Ok(Some(Self {
                     transaction_version: txn_version,
@@ -180,6 +219,7 @@ impl TokenActivityV2 {
                     token_standard: TokenStandard::V2.to_string(),
                     is_fungible_v2: None,
                     transaction_timestamp: txn_timestamp,
+                    is_transient: Some(is_transient),
                 }));
             }
         }
@@ -272,8 +312,218 @@ impl TokenActivityV2 {
                 token_standard: TokenStandard::V1.to_string(),
                 is_fungible_v2: None,
                 transaction_timestamp: txn_timestamp,
+                is_transient: None,
             }));
         }
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        object_models::v2_object_utils::ObjectAggregatedData,
+        token_v2_models::v2_token_utils::TransferEvent,
+    };
+    use aptos_protos::transaction::v1::EventKey;
+    use chrono::NaiveDateTime;
+
+    const TOKEN_ADDRESS: &str =
+        "0x00000000000000000000000000000000000000000000000000000000000000ab";
+
+    fn burn_event() -> Event {
+        Event {
+            key: Some(EventKey {
+                creation_number: 0,
+                account_address: "0xc".to_string(),
+            }),
+            sequence_number: 0,
+            r#type: None,
+            type_str: "0x4::collection::BurnEvent".to_string(),
+            data: format!(r#"{{"index": "1", "token": "{}"}}"#, TOKEN_ADDRESS),
+        }
+    }
+
+    async fn get_burn_activity(
+        token_v2_metadata: &ObjectAggregatedDataMapping,
+        tokens_minted: &TokenV2Minted,
+    ) -> TokenActivityV2 {
+        TokenActivityV2::get_nft_v2_from_parsed_event(
+            &burn_event(),
+            1,
+            NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            0,
+            &None,
+            token_v2_metadata,
+            tokens_minted,
+            &TokenV2Transferred::new(),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+    }
+
+    async fn burn_activity(tokens_minted: &TokenV2Minted) -> TokenActivityV2 {
+        get_burn_activity(&ObjectAggregatedDataMapping::new(), tokens_minted).await
+    }
+
+    async fn burn_activity_with_metadata(
+        token_v2_metadata: &ObjectAggregatedDataMapping,
+    ) -> TokenActivityV2 {
+        get_burn_activity(token_v2_metadata, &TokenV2Minted::new()).await
+    }
+
+    #[tokio::test]
+    async fn test_burn_of_token_minted_in_same_transaction_is_transient() {
+        let tokens_minted = TokenV2Minted::from_iter([TOKEN_ADDRESS.to_string()]);
+        let activity = burn_activity(&tokens_minted).await;
+        assert_eq!(activity.token_data_id, TOKEN_ADDRESS);
+        assert_eq!(activity.is_transient, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_is_fungible_v2_from_object_metadata() {
+        let mut token_v2_metadata = ObjectAggregatedDataMapping::new();
+        token_v2_metadata.insert(TOKEN_ADDRESS.to_string(), ObjectAggregatedData::default());
+        let activity = burn_activity_with_metadata(&token_v2_metadata).await;
+        assert_eq!(activity.is_fungible_v2, Some(false));
+
+        token_v2_metadata
+            .get_mut(TOKEN_ADDRESS)
+            .unwrap()
+            .fungible_asset_metadata = Some(
+            serde_json::from_value(serde_json::json!({
+                "name": "Fungible Token",
+                "symbol": "FT",
+                "decimals": 0,
+                "icon_uri": "",
+                "project_uri": "",
+            }))
+            .unwrap(),
+        );
+        let activity = burn_activity_with_metadata(&token_v2_metadata).await;
+        assert_eq!(activity.is_fungible_v2, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_missing_metadata_is_counted() {
+        let counter =
+            TOKEN_V2_MISSING_METADATA_COUNT.with_label_values(&["0x4::collection::BurnEvent"]);
+        let count = counter.get();
+        burn_activity(&TokenV2Minted::new()).await;
+        assert!(counter.get() > count);
+    }
+
+    fn transfer_event_without_to() -> Event {
+        Event {
+            key: Some(EventKey {
+                creation_number: 0,
+                account_address: TOKEN_ADDRESS.to_string(),
+            }),
+            sequence_number: 0,
+            r#type: None,
+            type_str: "0x1::object::TransferEvent".to_string(),
+            data: format!(r#"{{"from": "0xc", "object": "{}"}}"#, TOKEN_ADDRESS),
+        }
+    }
+
+    async fn transfer_activity(
+        event_index: i64,
+        tokens_transferred: &TokenV2Transferred,
+    ) -> TokenActivityV2 {
+        let mut token_v2_metadata = ObjectAggregatedDataMapping::new();
+        token_v2_metadata.insert(TOKEN_ADDRESS.to_string(), ObjectAggregatedData {
+            object: serde_json::from_value(serde_json::json!({
+                "object_core": {
+                    "allow_ungated_transfer": true,
+                    "guid_creation_num": "1",
+                    "owner": "0xd",
+                },
+                "state_key_hash": "",
+            }))
+            .unwrap(),
+            ..ObjectAggregatedData::default()
+        });
+        TokenActivityV2::get_nft_v2_from_parsed_event(
+            &transfer_event_without_to(),
+            1,
+            NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            event_index,
+            &None,
+            &token_v2_metadata,
+            &TokenV2Minted::new(),
+            tokens_transferred,
+        )
+        .await
+        .unwrap()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_transfer_without_to_address_uses_object_owner() {
+        let transfer = TransferEvent::from_event(&transfer_event_without_to(), 1)
+            .unwrap()
+            .unwrap();
+        let tokens_transferred =
+            TokenV2Transferred::from_iter([(TOKEN_ADDRESS.to_string(), vec![
+                (0, transfer.clone()),
+                (1, transfer),
+            ])]);
+
+        // Last transfer of the object in the transaction
+        let activity = transfer_activity(1, &tokens_transferred).await;
+        assert_eq!(activity.from_address, Some(standardize_address("0xc")));
+        assert_eq!(activity.to_address, Some(standardize_address("0xd")));
+
+        // Transferred again afterwards, so the object owner isn't this receiver
+        let activity = transfer_activity(0, &tokens_transferred).await;
+        assert_eq!(activity.from_address, Some(standardize_address("0xc")));
+        assert_eq!(activity.to_address, None);
+    }
+
+    #[tokio::test]
+    async fn test_transient_burn_from_address_is_last_receiver() {
+        let transfer = |to: &str| -> TransferEvent {
+            serde_json::from_value(serde_json::json!({
+                "from": "0xc",
+                "to": to,
+                "object": TOKEN_ADDRESS,
+            }))
+            .unwrap()
+        };
+        let tokens_transferred =
+            TokenV2Transferred::from_iter([(TOKEN_ADDRESS.to_string(), vec![
+                (0, transfer("0xd")),
+                (1, transfer("0xe")),
+                // After the burn at index 2
+                (3, transfer("0xf")),
+            ])]);
+        let activity = TokenActivityV2::get_nft_v2_from_parsed_event(
+            &burn_event(),
+            1,
+            NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            2,
+            &None,
+            &ObjectAggregatedDataMapping::new(),
+            &TokenV2Minted::from_iter([TOKEN_ADDRESS.to_string()]),
+            &tokens_transferred,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(activity.is_transient, Some(true));
+        assert_eq!(activity.from_address, Some(standardize_address("0xe")));
+
+        // Never transferred, so the owner is unknown
+        let activity = burn_activity(&TokenV2Minted::from_iter([TOKEN_ADDRESS.to_string()])).await;
+        assert_eq!(activity.from_address, None);
+    }
+
+    #[tokio::test]
+    async fn test_burn_of_existing_token_is_not_transient() {
+        let activity = burn_activity(&TokenV2Minted::new()).await;
+        assert_eq!(activity.token_data_id, TOKEN_ADDRESS);
+        assert_eq!(activity.is_transient, Some(false));
+    }
+}
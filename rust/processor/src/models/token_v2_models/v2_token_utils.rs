@@ -8,7 +8,7 @@ use crate::{
     models::{
         coin_models::coin_utils::COIN_ADDR,
         default_models::move_resources::MoveResource,
-        object_models::v2_object_utils::{CurrentObjectPK, ObjectCore},
+        object_models::v2_object_utils::{CurrentObjectPK, EventIndex, ObjectCore},
         token_models::token_utils::{NAME_LENGTH, URI_LENGTH},
     },
     utils::util::{
@@ -33,6 +33,8 @@ pub const DEFAULT_OWNER_ADDRESS: &str = "unknown";
 /// Maps address to burn event. If it's an old event previous_owner will be empty
 pub type TokenV2Burned = AHashMap<CurrentObjectPK, Burn>;
 pub type TokenV2Minted = AHashSet<CurrentObjectPK>;
+/// Maps object address to its transfer events in a transaction, in event order with their event index
+pub type TokenV2Transferred = AHashMap<CurrentObjectPK, Vec<(EventIndex, TransferEvent)>>;
 pub type TokenV2MintedPK = (CurrentObjectPK, i64);
 
 /// Tracks which token standard a token / collection is built upon
@@ -310,6 +312,16 @@ pub struct Mint {
 }
 
 impl Mint {
+    pub fn from_event(event: &Event, txn_version: i64) -> anyhow::Result<Option<Self>> {
+        if let Some(V2TokenEvent::Mint(inner)) =
+            V2TokenEvent::from_event(event.type_str.as_str(), &event.data, txn_version).unwrap()
+        {
+            Ok(Some(inner))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn get_token_address(&self) -> String {
         standardize_address(&self.token)
     }
@@ -390,7 +402,9 @@ impl Burn {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TransferEvent {
+    #[serde(default)]
     from: String,
+    #[serde(default)]
     to: String,
     object: String,
 }
@@ -414,6 +428,16 @@ impl TransferEvent {
         standardize_address(&self.to)
     }
 
+    /// None if the event doesn't have a from address
+    pub fn try_get_from_address(&self) -> Option<String> {
+        (!self.from.is_empty()).then(|| self.get_from_address())
+    }
+
+    /// None if the event doesn't have a to address
+    pub fn try_get_to_address(&self) -> Option<String> {
+        (!self.to.is_empty()).then(|| self.get_to_address())
+    }
+
     pub fn get_object_address(&self) -> String {
         standardize_address(&self.object)
     }
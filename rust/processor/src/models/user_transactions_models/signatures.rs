@@ -51,7 +51,11 @@ impl Signature {
         transaction_version: i64,
         transaction_block_height: i64,
     ) -> Result<Vec<Self>> {
-        match s.signature.as_ref().unwrap() {
+        match s
+            .signature
+            .as_ref()
+            .context("Transaction signature is missing")?
+        {
             SignatureEnum::Ed25519(sig) => Ok(vec![Self::parse_ed25519_signature(
                 sig,
                 sender,
@@ -120,13 +124,13 @@ impl Signature {
         t: &TransactionSignaturePb,
         transaction_version: i64,
     ) -> Option<String> {
-        let sig = t.signature.as_ref().unwrap_or_else(|| {
-            tracing::error!(
+        let Some(sig) = t.signature.as_ref() else {
+            tracing::warn!(
                 transaction_version = transaction_version,
-                "Transaction signature is missing"
+                "Transaction signature is missing, assuming no fee payer"
             );
-            panic!("Transaction signature is missing");
-        });
+            return None;
+        };
         match sig {
             SignatureEnum::FeePayer(sig) => Some(standardize_address(&sig.fee_payer_address)),
             _ => None,
@@ -176,11 +180,20 @@ impl Signature {
             .map(|index| *index as usize)
             .collect();
         for (index, signature) in s.signatures.iter().enumerate() {
-            let public_key = s
-                .public_keys
-                .get(public_key_indices.clone()[index])
-                .unwrap()
-                .clone();
+            let Some(public_key) = public_key_indices
+                .get(index)
+                .and_then(|public_key_index| s.public_keys.get(*public_key_index))
+            else {
+                tracing::warn!(
+                    transaction_version = transaction_version,
+                    multi_sig_index = index,
+                    "Public key missing for multi ed25519 signature, skipping it"
+                );
+                PROCESSOR_UNKNOWN_TYPE_COUNT
+                    .with_label_values(&["MultiEd25519Signature"])
+                    .inc();
+                continue;
+            };
             signatures.push(Self {
                 transaction_version,
                 transaction_block_height,
@@ -214,7 +227,7 @@ impl Signature {
         let mut signatures = Vec::default();
         // process sender signature
         signatures.append(&mut Self::parse_multi_agent_signature_helper(
-            s.sender.as_ref().unwrap(),
+            s.sender.as_ref().context("Sender signature is missing")?,
             sender,
             transaction_version,
             transaction_block_height,
@@ -249,7 +262,7 @@ impl Signature {
         let mut signatures = Vec::default();
         // process sender signature
         signatures.append(&mut Self::parse_multi_agent_signature_helper(
-            s.sender.as_ref().unwrap(),
+            s.sender.as_ref().context("Sender signature is missing")?,
             sender,
             transaction_version,
             transaction_block_height,
@@ -272,6 +285,29 @@ impl Signature {
                 Some(&address.to_string()),
             ));
         }
+        // process fee payer signature, indexed after the secondary signers
+        match s.fee_payer_signer.as_ref() {
+            Some(fee_payer_signer) => {
+                signatures.append(&mut Self::parse_multi_agent_signature_helper(
+                    fee_payer_signer,
+                    sender,
+                    transaction_version,
+                    transaction_block_height,
+                    false,
+                    s.secondary_signer_addresses.len() as i64,
+                    Some(&s.fee_payer_address),
+                ));
+            },
+            None => {
+                tracing::warn!(
+                    transaction_version = transaction_version,
+                    "Fee payer signature is missing, skipping it"
+                );
+                PROCESSOR_UNKNOWN_TYPE_COUNT
+                    .with_label_values(&["FeePayerSignature"])
+                    .inc();
+            },
+        }
         Ok(signatures)
     }
 
@@ -571,3 +607,130 @@ impl Signature {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multi_ed25519_signature(public_key_indices: Vec<u32>) -> TransactionSignaturePb {
+        TransactionSignaturePb {
+            signature: Some(SignatureEnum::MultiEd25519(MultiEd25519SignaturePb {
+                public_keys: vec![vec![1; 32], vec![2; 32]],
+                signatures: vec![vec![3; 64], vec![4; 64]],
+                threshold: 2,
+                public_key_indices,
+            })),
+            ..TransactionSignaturePb::default()
+        }
+    }
+
+    fn ed25519_account_signature(key: u8) -> ProtoAccountSignature {
+        ProtoAccountSignature {
+            signature: Some(AccountSignatureEnum::Ed25519(Ed25519SignaturePB {
+                public_key: vec![key; 32],
+                signature: vec![key; 64],
+            })),
+            ..ProtoAccountSignature::default()
+        }
+    }
+
+    #[test]
+    fn test_fee_payer_signature_records_fee_payer() {
+        let signature = TransactionSignaturePb {
+            signature: Some(SignatureEnum::FeePayer(ProtoFeePayerSignature {
+                sender: Some(ed25519_account_signature(1)),
+                secondary_signer_addresses: vec!["0x2".to_string()],
+                secondary_signers: vec![ed25519_account_signature(2)],
+                fee_payer_address: "0x3".to_string(),
+                fee_payer_signer: Some(ed25519_account_signature(3)),
+            })),
+            ..TransactionSignaturePb::default()
+        };
+        let signatures =
+            Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).unwrap();
+        assert_eq!(signatures.len(), 3);
+
+        let sender = &signatures[0];
+        assert_eq!(sender.signer, standardize_address("0x1"));
+        assert!(sender.is_sender_primary);
+
+        let secondary_signer = &signatures[1];
+        assert_eq!(secondary_signer.signer, standardize_address("0x2"));
+        assert!(!secondary_signer.is_sender_primary);
+        assert_eq!(secondary_signer.multi_agent_index, 0);
+
+        let fee_payer = &signatures[2];
+        assert_eq!(fee_payer.signer, standardize_address("0x3"));
+        assert!(!fee_payer.is_sender_primary);
+        assert_eq!(fee_payer.multi_agent_index, 1);
+        assert_eq!(fee_payer.public_key, format!("0x{}", hex::encode([3; 32])));
+        assert_eq!(
+            Signature::get_fee_payer_address(&signature, 1),
+            Some(standardize_address("0x3"))
+        );
+    }
+
+    #[test]
+    fn test_fee_payer_signature_without_fee_payer_signer() {
+        let signature = TransactionSignaturePb {
+            signature: Some(SignatureEnum::FeePayer(ProtoFeePayerSignature {
+                sender: Some(ed25519_account_signature(1)),
+                secondary_signer_addresses: vec!["0x2".to_string()],
+                secondary_signers: vec![ed25519_account_signature(2)],
+                fee_payer_address: "0x3".to_string(),
+                fee_payer_signer: None,
+            })),
+            ..TransactionSignaturePb::default()
+        };
+        // Only the fee payer row is skipped
+        let signatures =
+            Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).unwrap();
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(signatures[0].signer, standardize_address("0x1"));
+        assert!(signatures[0].is_sender_primary);
+        assert_eq!(signatures[1].signer, standardize_address("0x2"));
+        assert!(!signatures[1].is_sender_primary);
+    }
+
+    #[test]
+    fn test_missing_signature_is_an_error() {
+        let signature = TransactionSignaturePb::default();
+        assert!(Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).is_err());
+        assert_eq!(Signature::get_fee_payer_address(&signature, 1), None);
+    }
+
+    #[test]
+    fn test_multi_agent_missing_sender_is_an_error() {
+        let signature = TransactionSignaturePb {
+            signature: Some(SignatureEnum::MultiAgent(
+                ProtoMultiAgentSignature::default(),
+            )),
+            ..TransactionSignaturePb::default()
+        };
+        assert!(Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_multi_ed25519_skips_signatures_without_public_key() {
+        // The second public key index is out of range
+        let signature = multi_ed25519_signature(vec![0, 5]);
+        let signatures =
+            Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).unwrap();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0].multi_sig_index, 0);
+        assert_eq!(
+            signatures[0].public_key,
+            format!("0x{}", hex::encode([1; 32]))
+        );
+
+        // Fewer public key indices than signatures
+        let signature = multi_ed25519_signature(vec![1]);
+        let signatures =
+            Signature::from_user_transaction(&signature, &"0x1".to_string(), 1, 1).unwrap();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(
+            signatures[0].public_key,
+            format!("0x{}", hex::encode([2; 32]))
+        );
+    }
+}
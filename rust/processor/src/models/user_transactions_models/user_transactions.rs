@@ -10,9 +10,12 @@
 use super::signatures::Signature;
 use crate::{
     schema::user_transactions,
-    utils::util::{
-        get_entry_function_from_user_request, parse_timestamp, standardize_address,
-        u64_to_bigdecimal,
+    utils::{
+        counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
+        util::{
+            get_entry_function_from_user_request, parse_timestamp, standardize_address,
+            u64_to_bigdecimal,
+        },
     },
 };
 use aptos_protos::{
@@ -84,7 +87,7 @@ impl UserTransaction {
         )
     }
 
-    /// Empty vec if signature is None
+    /// Empty vec if signature is None or can't be parsed
     pub fn get_signatures(
         user_request: &UserTransactionRequest,
         version: i64,
@@ -95,7 +98,17 @@ impl UserTransaction {
             .as_ref()
             .map(|s| {
                 Signature::from_user_transaction(s, &user_request.sender, version, block_height)
-                    .unwrap()
+                    .unwrap_or_else(|e| {
+                        tracing::error!(
+                            transaction_version = version,
+                            error = ?e,
+                            "Failed to parse signatures, skipping them"
+                        );
+                        PROCESSOR_UNKNOWN_TYPE_COUNT
+                            .with_label_values(&["Signature"])
+                            .inc();
+                        vec![]
+                    })
             })
             .unwrap_or_default()
     }
@@ -5,7 +5,9 @@ use super::{ProcessingResult, ProcessorName, ProcessorTrait};
 use crate::{
     models::account_transaction_models::account_transactions::AccountTransaction,
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+    utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool, TableFieldCount,
+    },
 };
 use ahash::AHashMap;
 use anyhow::bail;
@@ -18,13 +20,19 @@ use tracing::error;
 pub struct AccountTransactionsProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl AccountTransactionsProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -47,6 +55,7 @@ async fn insert_to_db(
     end_version: u64,
     account_transactions: &[AccountTransaction],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -62,6 +71,7 @@ async fn insert_to_db(
             "account_transactions",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     )
     .await?;
     Ok(())
@@ -98,7 +108,7 @@ impl ProcessorTrait for AccountTransactionsProcessor {
         _db_chain_id: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut account_transactions = AHashMap::new();
 
@@ -124,6 +134,7 @@ impl ProcessorTrait for AccountTransactionsProcessor {
             end_version,
             &account_transactions,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -152,4 +163,15 @@ impl ProcessorTrait for AccountTransactionsProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![TableFieldCount::new::<AccountTransaction>(
+            "account_transactions",
+            &["inserted_at"],
+        )]
+    }
 }
@@ -13,7 +13,10 @@ use crate::{
     schema,
     utils::{
         counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
         util::standardize_address,
     },
 };
@@ -44,6 +47,7 @@ pub struct AnsProcessor {
     connection_pool: PgDbPool,
     config: AnsProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl AnsProcessor {
@@ -51,6 +55,7 @@ impl AnsProcessor {
         connection_pool: PgDbPool,
         config: AnsProcessorConfig,
         per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
     ) -> Self {
         tracing::info!(
             ans_v1_primary_names_table_handle = config.ans_v1_primary_names_table_handle,
@@ -62,6 +67,7 @@ impl AnsProcessor {
             connection_pool,
             config,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -91,6 +97,7 @@ async fn insert_to_db(
     current_ans_primary_names_v2: &[CurrentAnsPrimaryNameV2],
     ans_primary_names_v2: &[AnsPrimaryNameV2],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -106,12 +113,14 @@ async fn insert_to_db(
             "current_ans_lookup",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let al = execute_in_chunks(
         conn.clone(),
         insert_ans_lookups_query,
         ans_lookups,
         get_config_table_chunk_size::<AnsLookup>("ans_lookup", per_table_chunk_sizes),
+        db_write_config,
     );
     let capn = execute_in_chunks(
         conn.clone(),
@@ -121,12 +130,14 @@ async fn insert_to_db(
             "current_ans_primary_name",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let apn = execute_in_chunks(
         conn.clone(),
         insert_ans_primary_names_query,
         ans_primary_names,
         get_config_table_chunk_size::<AnsPrimaryName>("ans_primary_name", per_table_chunk_sizes),
+        db_write_config,
     );
     let cal_v2 = execute_in_chunks(
         conn.clone(),
@@ -136,12 +147,14 @@ async fn insert_to_db(
             "current_ans_lookup_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let al_v2 = execute_in_chunks(
         conn.clone(),
         insert_ans_lookups_v2_query,
         ans_lookups_v2,
         get_config_table_chunk_size::<AnsLookupV2>("ans_lookup_v2", per_table_chunk_sizes),
+        db_write_config,
     );
     let capn_v2 = execute_in_chunks(
         conn.clone(),
@@ -151,6 +164,7 @@ async fn insert_to_db(
             "current_ans_primary_name_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let apn_v2 = execute_in_chunks(
         conn,
@@ -160,6 +174,7 @@ async fn insert_to_db(
             "ans_primary_name_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
 
     let (cal_res, al_res, capn_res, apn_res, cal_v2_res, al_v2_res, capn_v2_res, apn_v2_res) =
@@ -368,7 +383,7 @@ impl ProcessorTrait for AnsProcessor {
         _db_chain_id: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let (
             all_current_ans_lookups,
@@ -404,6 +419,7 @@ impl ProcessorTrait for AnsProcessor {
             &all_current_ans_primary_names_v2,
             &all_ans_primary_names_v2,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -433,6 +449,27 @@ impl ProcessorTrait for AnsProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<CurrentAnsLookup>("current_ans_lookup", &["inserted_at"]),
+            TableFieldCount::new::<AnsLookup>("ans_lookup", &["inserted_at"]),
+            TableFieldCount::new::<CurrentAnsPrimaryName>("current_ans_primary_name", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<AnsPrimaryName>("ans_primary_name", &["inserted_at"]),
+            TableFieldCount::new::<CurrentAnsLookupV2>("current_ans_lookup_v2", &["inserted_at"]),
+            TableFieldCount::new::<AnsLookupV2>("ans_lookup_v2", &["inserted_at"]),
+            TableFieldCount::new::<CurrentAnsPrimaryNameV2>("current_ans_primary_name_v2", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<AnsPrimaryNameV2>("ans_primary_name_v2", &["inserted_at"]),
+        ]
+    }
 }
 
 fn parse_ans(
@@ -13,7 +13,9 @@ use crate::{
         fungible_asset_models::v2_fungible_asset_activities::CurrentCoinBalancePK,
     },
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+    utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool, TableFieldCount,
+    },
 };
 use ahash::AHashMap;
 use anyhow::{bail, Context};
@@ -24,7 +26,7 @@ use diesel::{
     query_builder::QueryFragment,
     ExpressionMethods,
 };
-use std::fmt::Debug;
+use std::{collections::hash_map::Entry, fmt::Debug};
 use tracing::error;
 
 pub const APTOS_COIN_TYPE_STR: &str = "0x1::aptos_coin::AptosCoin";
@@ -32,13 +34,19 @@ pub const APTOS_COIN_TYPE_STR: &str = "0x1::aptos_coin::AptosCoin";
 pub struct CoinProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl CoinProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -65,6 +73,7 @@ async fn insert_to_db(
     current_coin_balances: &[CurrentCoinBalance],
     coin_supply: &[CoinSupply],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -78,18 +87,21 @@ async fn insert_to_db(
         insert_coin_activities_query,
         coin_activities,
         get_config_table_chunk_size::<CoinActivity>("coin_activities", per_table_chunk_sizes),
+        db_write_config,
     );
     let ci = execute_in_chunks(
         conn.clone(),
         insert_coin_infos_query,
         coin_infos,
         get_config_table_chunk_size::<CoinInfo>("coin_infos", per_table_chunk_sizes),
+        db_write_config,
     );
     let cb = execute_in_chunks(
         conn.clone(),
         insert_coin_balances_query,
         coin_balances,
         get_config_table_chunk_size::<CoinBalance>("coin_balances", per_table_chunk_sizes),
+        db_write_config,
     );
     let ccb = execute_in_chunks(
         conn.clone(),
@@ -99,12 +111,14 @@ async fn insert_to_db(
             "current_coin_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cs = execute_in_chunks(
         conn,
         insert_coin_supply_query,
         coin_supply,
         get_config_table_chunk_size::<CoinSupply>("coin_supply", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let (ca_res, ci_res, cb_res, ccb_res, cs_res) = tokio::join!(ca, ci, cb, ccb, cs);
@@ -176,11 +190,19 @@ fn insert_coin_balances_query(
 ) {
     use schema::coin_balances::dsl::*;
 
+    // The conflict target includes the version, so this only rewrites rows when the same
+    // version is reprocessed, e.g. after a parser fix
     (
         diesel::insert_into(schema::coin_balances::table)
             .values(items_to_insert)
             .on_conflict((transaction_version, owner_address, coin_type_hash))
-            .do_nothing(),
+            .do_update()
+            .set((
+                coin_type.eq(excluded(coin_type)),
+                amount.eq(excluded(amount)),
+                transaction_timestamp.eq(excluded(transaction_timestamp)),
+                inserted_at.eq(excluded(inserted_at)),
+            )),
         None,
     )
 }
@@ -225,6 +247,33 @@ fn insert_coin_supply_query(
     )
 }
 
+/// Coin info can't be modified, so for each coin type we only want to keep the version it
+/// was created at. This keeps the `transaction_version_created` guard in
+/// `insert_coin_infos_query` from being fed a later version regardless of input order.
+fn keep_earliest_coin_info(
+    all_coin_infos: &mut AHashMap<String, CoinInfo>,
+    coin_type: String,
+    coin_info: CoinInfo,
+) {
+    match all_coin_infos.entry(coin_type) {
+        Entry::Occupied(mut existing) => {
+            if coin_info.transaction_version_created < existing.get().transaction_version_created {
+                existing.insert(coin_info);
+            }
+        },
+        Entry::Vacant(entry) => {
+            entry.insert(coin_info);
+        },
+    }
+}
+
+/// Sorts by coin_type_hash, the conflict key of `insert_coin_infos_query`, so that rows are
+/// always written in the same order. Ties are broken by coin_type.
+fn sort_coin_infos_by_pk(coin_infos: &mut [CoinInfo]) {
+    coin_infos
+        .sort_by(|a, b| (&a.coin_type_hash, &a.coin_type).cmp(&(&b.coin_type_hash, &b.coin_type)));
+}
+
 #[async_trait]
 impl ProcessorTrait for CoinProcessor {
     fn name(&self) -> &'static str {
@@ -239,7 +288,7 @@ impl ProcessorTrait for CoinProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let (
             all_coin_activities,
@@ -266,9 +315,8 @@ impl ProcessorTrait for CoinProcessor {
                 all_coin_activities.append(&mut coin_activities);
                 all_coin_balances.append(&mut coin_balances);
                 all_coin_supply.append(&mut coin_supply);
-                // For coin infos, we only want to keep the first version, so insert only if key is not present already
                 for (key, value) in coin_infos {
-                    all_coin_infos.entry(key).or_insert(value);
+                    keep_earliest_coin_info(&mut all_coin_infos, key, value);
                 }
                 all_current_coin_balances.extend(current_coin_balances);
             }
@@ -278,7 +326,7 @@ impl ProcessorTrait for CoinProcessor {
                 .collect::<Vec<CurrentCoinBalance>>();
 
             // Sort by PK
-            all_coin_infos.sort_by(|a, b| a.coin_type.cmp(&b.coin_type));
+            sort_coin_infos_by_pk(&mut all_coin_infos);
             all_current_coin_balances.sort_by(|a, b| {
                 (&a.owner_address, &a.coin_type).cmp(&(&b.owner_address, &b.coin_type))
             });
@@ -308,6 +356,7 @@ impl ProcessorTrait for CoinProcessor {
             &all_current_coin_balances,
             &all_coin_supply,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -337,4 +386,109 @@ impl ProcessorTrait for CoinProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<CoinActivity>("coin_activities", &["inserted_at"]),
+            TableFieldCount::new::<CoinInfo>("coin_infos", &["inserted_at"]),
+            TableFieldCount::new::<CoinBalance>("coin_balances", &["inserted_at"]),
+            TableFieldCount::new::<CurrentCoinBalance>("current_coin_balances", &["inserted_at"]),
+            TableFieldCount::new::<CoinSupply>("coin_supply", &["inserted_at"]),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin_info(coin_type: &str, transaction_version_created: i64) -> CoinInfo {
+        CoinInfo {
+            coin_type_hash: coin_type.to_string(),
+            coin_type: coin_type.to_string(),
+            transaction_version_created,
+            creator_address: "0x1".to_string(),
+            name: "Aptos Coin".to_string(),
+            symbol: "APT".to_string(),
+            decimals: 8,
+            transaction_created_timestamp: chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+            supply_aggregator_table_handle: None,
+            supply_aggregator_table_key: None,
+        }
+    }
+
+    #[test]
+    fn test_coin_balances_are_updated_on_reprocessing() {
+        let (query, where_clause) = insert_coin_balances_query(vec![CoinBalance {
+            transaction_version: 1,
+            owner_address: "0x1".to_string(),
+            coin_type_hash: "hash".to_string(),
+            coin_type: APTOS_COIN_TYPE_STR.to_string(),
+            amount: bigdecimal::BigDecimal::from(100),
+            transaction_timestamp: chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap(),
+        }]);
+        let sql = diesel::debug_query::<Pg, _>(&query).to_string();
+        assert!(sql.contains(
+            r#"ON CONFLICT ("transaction_version", "owner_address", "coin_type_hash") DO UPDATE SET"#
+        ));
+        assert!(sql.contains(r#""amount" = excluded."amount""#));
+        assert!(where_clause.is_none());
+    }
+
+    #[test]
+    fn test_sort_coin_infos_by_pk() {
+        let mut coin_infos = vec![
+            CoinInfo {
+                coin_type_hash: "b".to_string(),
+                ..coin_info("0x1::a::A", 1)
+            },
+            CoinInfo {
+                coin_type_hash: "a".to_string(),
+                ..coin_info("0x1::c::C", 1)
+            },
+            CoinInfo {
+                coin_type_hash: "a".to_string(),
+                ..coin_info("0x1::b::B", 1)
+            },
+        ];
+        sort_coin_infos_by_pk(&mut coin_infos);
+        let sorted = coin_infos
+            .iter()
+            .map(|coin_info| {
+                (
+                    coin_info.coin_type_hash.as_str(),
+                    coin_info.coin_type.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![
+            ("a", "0x1::b::B"),
+            ("a", "0x1::c::C"),
+            ("b", "0x1::a::A"),
+        ]);
+    }
+
+    #[test]
+    fn test_keep_earliest_coin_info() {
+        let mut all_coin_infos = AHashMap::new();
+        for version in [20, 10, 30] {
+            keep_earliest_coin_info(
+                &mut all_coin_infos,
+                APTOS_COIN_TYPE_STR.to_string(),
+                coin_info(APTOS_COIN_TYPE_STR, version),
+            );
+        }
+        assert_eq!(all_coin_infos.len(), 1);
+        assert_eq!(
+            all_coin_infos
+                .get(APTOS_COIN_TYPE_STR)
+                .unwrap()
+                .transaction_version_created,
+            10
+        );
+    }
 }
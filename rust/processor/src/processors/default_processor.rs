@@ -12,7 +12,9 @@ use crate::{
         write_set_changes::{WriteSetChangeDetail, WriteSetChangeModel},
     },
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+    utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool, TableFieldCount,
+    },
 };
 use ahash::AHashMap;
 use anyhow::bail;
@@ -30,13 +32,19 @@ use tracing::error;
 pub struct DefaultProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl DefaultProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -68,6 +76,7 @@ async fn insert_to_db(
         &[TableMetadata],
     ),
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -81,6 +90,7 @@ async fn insert_to_db(
         insert_transactions_query,
         txns,
         get_config_table_chunk_size::<TransactionModel>("transactions", per_table_chunk_sizes),
+        db_write_config,
     );
     let bmt_res = execute_in_chunks(
         conn.clone(),
@@ -90,6 +100,7 @@ async fn insert_to_db(
             "block_metadata_transactions",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let wst_res = execute_in_chunks(
         conn.clone(),
@@ -99,12 +110,14 @@ async fn insert_to_db(
             "write_set_changes",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let mm_res = execute_in_chunks(
         conn.clone(),
         insert_move_modules_query,
         move_modules,
         get_config_table_chunk_size::<MoveModule>("move_modules", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let mr_res = execute_in_chunks(
@@ -112,6 +125,7 @@ async fn insert_to_db(
         insert_move_resources_query,
         move_resources,
         get_config_table_chunk_size::<MoveResource>("move_resources", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let ti_res = execute_in_chunks(
@@ -119,6 +133,7 @@ async fn insert_to_db(
         insert_table_items_query,
         table_items,
         get_config_table_chunk_size::<TableItem>("table_items", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let cti_res = execute_in_chunks(
@@ -129,6 +144,7 @@ async fn insert_to_db(
             "current_table_items",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
 
     let tm_res = execute_in_chunks(
@@ -136,6 +152,7 @@ async fn insert_to_db(
         insert_table_metadata_query,
         table_metadata,
         get_config_table_chunk_size::<TableMetadata>("table_metadatas", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let (txns_res, bmt_res, wst_res, mm_res, mr_res, ti_res, cti_res, tm_res) =
@@ -312,7 +329,7 @@ impl ProcessorTrait for DefaultProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
         let (
             txns,
             block_metadata_transactions,
@@ -341,6 +358,7 @@ impl ProcessorTrait for DefaultProcessor {
                 &table_metadata,
             ),
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -369,6 +387,26 @@ impl ProcessorTrait for DefaultProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<TransactionModel>("transactions", &["inserted_at"]),
+            TableFieldCount::new::<BlockMetadataTransactionModel>(
+                "block_metadata_transactions",
+                &["inserted_at"],
+            ),
+            TableFieldCount::new::<WriteSetChangeModel>("write_set_changes", &["inserted_at"]),
+            TableFieldCount::new::<MoveModule>("move_modules", &["inserted_at"]),
+            TableFieldCount::new::<MoveResource>("move_resources", &["inserted_at"]),
+            TableFieldCount::new::<TableItem>("table_items", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTableItem>("current_table_items", &["inserted_at"]),
+            TableFieldCount::new::<TableMetadata>("table_metadatas", &["inserted_at"]),
+        ]
+    }
 }
 
 fn process_transactions(
@@ -4,18 +4,21 @@
 use super::{ProcessingResult, ProcessorName, ProcessorTrait};
 use crate::utils::database::PgPoolConnection;
 use crate::utils::util::{
-    is_multisig_wallet_created_transaction, standardize_address, truncate_str,
+    is_multisig_wallet_created_transaction, truncate_str, try_standardize_address,
 };
 use crate::{
     models::events_models::events::EventModel,
     schema,
     utils::{
         counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
     },
 };
-use ahash::AHashMap;
-use anyhow::bail;
+use ahash::{AHashMap, AHashSet};
+use anyhow::{bail, Context};
 use aptos_protos::transaction::v1::write_set_change::Change;
 use aptos_protos::transaction::v1::{
     transaction::TxnData, Event, EventKey, Transaction, WriteSetChange,
@@ -27,65 +30,163 @@ use diesel::{
     query_builder::QueryFragment,
     ExpressionMethods,
 };
-use once_cell::sync::Lazy;
+use regex::RegexSet;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
 use tracing::error;
 use tracing::log::info;
 
-static FILTERED_EVENTS: Lazy<Vec<&str>> = Lazy::new(|| {
-    vec![
-        "0x1::transaction_fee::FeeStatement",
-        "0x1::multisig_account::create_with_owners",
-    ]
-});
-static REQUIRED_EVENTS: Lazy<Vec<&str>> = Lazy::new(|| {
-    vec![
-        "0x111ae3e5bc816a5e63c2da97d0aa3886519e0cd5e4b046659fa35796bd11542a",
-        "0x9770fa9c725cbd97eb50b2be5f7416efdfd1f1554beb0750d4dae4c64e860da3",
-        "0x190d44266241744264b964a37b8f09863167a12d3e70cda39376cfb4e3561e12",
-        "0x61d2c22a6cb7831bee0f48363b0eec92369357aece0d1142062f7d5d85c7bef8",
-        "0xc7efb4076dbe143cbcd98cfaaa929ecfc8f299203dfff63b95ccb6bfe19850fa",
-        "0x48271d39d0b05bd6efca2278f22277d6fcc375504f9839fd73f74ace240861af",
-        "0x5ae6789dd2fec1a9ec9cccfb3acaf12e93d432f0a3a42c92fe1a9d490b7bbc06",
-        "0x31a6675cbe84365bf2b0cbce617ece6c47023ef70826533bde5203d32171dc3c",
-        "0xe11c12ec495f3989c35e1c6a0af414451223305b579291fc8f3d9d0575a23c26",
-        "0x584b50b999c78ade62f8359c91b5165ff390338d45f8e55969a04e65d76258c9",
-        "0xd520d8669b0a3de23119898dcdff3e0a27910db247663646ad18cf16e44c6f5",
-        "0xc0deb00c405f84c85dc13442e305df75d1288100cdd82675695f6148c7ece51c",
-        "0x17f1e926a81639e9557f4e4934df93452945ec30bc962e11351db59eb0d78c33",
-        "0x1::voting",
-        "0x1::aptos_governance",
-        "0x1::delegation_pool",
-        "0x05a97986a9d031c4567e15b797be516910cfcb4156312482efc6a19c0a30c948",
-        "0xfaf4e633ae9eb31366c9ca24214231760926576c7b625313b3688b5e900731f6",
-        "0x163df34fccbf003ce219d3f1d9e70d140b60622cb9dd47599c25fb2f797ba6e",
-        "0x4bf51972879e3b95c4781a5cdcb9e1ee24ef483e7d22f2d903626f126df62bd1",
-        "0x3c1d4a86594d681ff7e5d5a233965daeabdc6a15fe5672ceeda5260038857183",
-        "0xc6bc659f1649553c1a3fa05d9727433dc03843baac29473c817d06d39e7621ba",
-        "0x167f411fc5a678fb40d86e0af646fa8f62458b686ad8996215248447037af40c",
-        "0x1::multisig_account::CreateTransactionEvent",
-        "0x1::multisig_account::AddOwnersEvent",
-        "0x1::multisig_account::RemoveOwnersEvent",
-        "0x1::multisig_account::VoteEvent",
-        "0x1::multisig_account::TransactionExecutionSucceededEvent",
-        "0x1::multisig_account::TransactionExecutionFailedEvent",
-        "0x1::multisig_account::ExecuteRejectedTransactionEvent",
-        "0xccd1a84ccea93531d7f165b90134aa0415feb30e8757ab1632dac68c0055f5c2",
-    ]
-});
+/// How `event_filters` are matched against an event's type
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventFilterMode {
+    /// The event type contains the filter
+    #[default]
+    Contains,
+    /// The event type is equal to the filter
+    Exact,
+    /// The event type matches the filter as a regex, e.g. `^0x1::multisig_account::.*Event$`
+    Regex,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventsProcessorConfig {
+    /// Only events whose type matches one of these filters are stored, e.g.
+    /// `0x1::multisig_account` or a full event type. Empty means store all events.
+    #[serde(default)]
+    pub event_filters: Vec<String>,
+    #[serde(default)]
+    pub event_filter_mode: EventFilterMode,
+    /// Also store all events of modules published by these accounts, e.g. `0x1`
+    #[serde(default)]
+    pub account_prefixes: Vec<String>,
+    /// Never store events of these exact types, even if they match a filter above
+    #[serde(default)]
+    pub exclude_event_types: Vec<String>,
+    /// Never store events emitted by these entry functions, e.g.
+    /// `0x1::multisig_account::create_with_owners`
+    #[serde(default)]
+    pub exclude_entry_functions: Vec<String>,
+}
+
+/// Type filters from the config, with regexes compiled once up front
+#[derive(Clone, Debug)]
+pub enum EventTypeFilter {
+    Contains(Vec<String>),
+    Exact(AHashSet<String>),
+    Regex(RegexSet),
+}
+
+impl EventTypeFilter {
+    pub fn matches(&self, event_type: &str) -> bool {
+        match self {
+            Self::Contains(filters) => filters
+                .iter()
+                .any(|filter| event_type.contains(filter.as_str())),
+            Self::Exact(filters) => filters.contains(event_type),
+            Self::Regex(filters) => filters.is_match(event_type),
+        }
+    }
+}
+
+/// Events are stored if they match a type filter or an account prefix, or if there are neither,
+/// and aren't excluded by type or entry function
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    type_filter: Option<EventTypeFilter>,
+    // Standardized account addresses
+    account_prefixes: AHashSet<String>,
+    exclude_event_types: AHashSet<String>,
+    exclude_entry_functions: AHashSet<String>,
+}
+
+impl EventFilter {
+    pub fn new(config: &EventsProcessorConfig) -> anyhow::Result<Self> {
+        let type_filter = if config.event_filters.is_empty() {
+            None
+        } else {
+            Some(match config.event_filter_mode {
+                EventFilterMode::Contains => {
+                    EventTypeFilter::Contains(config.event_filters.clone())
+                },
+                EventFilterMode::Exact => {
+                    EventTypeFilter::Exact(config.event_filters.iter().cloned().collect())
+                },
+                EventFilterMode::Regex => EventTypeFilter::Regex(
+                    RegexSet::new(&config.event_filters).context("Invalid event filter regex")?,
+                ),
+            })
+        };
+        let account_prefixes = config
+            .account_prefixes
+            .iter()
+            .map(|prefix| {
+                try_standardize_address(prefix.trim_end_matches("::"))
+                    .context("Invalid event account prefix")
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            type_filter,
+            account_prefixes,
+            exclude_event_types: config.exclude_event_types.iter().cloned().collect(),
+            exclude_entry_functions: config.exclude_entry_functions.iter().cloned().collect(),
+        })
+    }
+
+    /// Whether an event should be stored, checking both the type filters and the exclusions
+    pub fn should_store(&self, event: &EventModel) -> bool {
+        self.matches(&event.type_)
+            && !self.exclude_event_types.contains(&event.type_)
+            && !self
+                .exclude_entry_functions
+                .contains(&event.entry_function_id_str)
+    }
+
+    /// Whether an event of the given type should be stored
+    pub fn matches(&self, event_type: &str) -> bool {
+        if self.type_filter.is_none() && self.account_prefixes.is_empty() {
+            return true;
+        }
+        self.type_filter
+            .as_ref()
+            .is_some_and(|type_filter| type_filter.matches(event_type))
+            || self.matches_account_prefix(event_type)
+    }
+
+    fn matches_account_prefix(&self, event_type: &str) -> bool {
+        if self.account_prefixes.is_empty() {
+            return false;
+        }
+        event_type
+            .split_once("::")
+            .and_then(|(address, _)| try_standardize_address(address).ok())
+            .is_some_and(|address| self.account_prefixes.contains(&address))
+    }
+}
+
 pub struct EventsProcessor {
     connection_pool: PgDbPool,
+    event_filter: EventFilter,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl EventsProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
-        Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        config: EventsProcessorConfig,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
             connection_pool,
+            event_filter: EventFilter::new(&config)?,
             per_table_chunk_sizes,
-        }
+            db_write_config,
+        })
     }
 }
 
@@ -107,6 +208,7 @@ async fn insert_to_db(
     end_version: u64,
     events: &[EventModel],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -119,6 +221,7 @@ async fn insert_to_db(
         insert_events_query,
         events,
         get_config_table_chunk_size::<EventModel>("events", per_table_chunk_sizes),
+        db_write_config,
     )
     .await?;
     Ok(())
@@ -139,11 +242,152 @@ fn insert_events_query(
             .set((
                 inserted_at.eq(excluded(inserted_at)),
                 indexed_type.eq(excluded(indexed_type)),
+                epoch.eq(excluded(epoch)),
             )),
         None,
     )
 }
 
+fn parse_transactions(transactions: &[Transaction], event_filter: &EventFilter) -> Vec<EventModel> {
+    let mut events = vec![];
+    for txn in transactions {
+        // Logs from parsing the transaction carry its version
+        tracing::info_span!("txn", version = txn.version)
+            .in_scope(|| parse_transaction(txn, event_filter, &mut events));
+    }
+    events
+}
+
+/// Appends the events to store from one transaction
+fn parse_transaction(txn: &Transaction, event_filter: &EventFilter, events: &mut Vec<EventModel>) {
+    let txn_version = txn.version as i64;
+    let block_height = txn.block_height as i64;
+    let epoch = txn.epoch as i64;
+    let txn_data = match txn.txn_data.as_ref() {
+        Some(data) => data,
+        None => {
+            tracing::warn!(
+                transaction_version = txn_version,
+                "Transaction data doesn't exist"
+            );
+            PROCESSOR_UNKNOWN_TYPE_COUNT
+                .with_label_values(&["EventsProcessor"])
+                .inc();
+            return;
+        },
+    };
+
+    let default = vec![];
+    let raw_events = match txn_data {
+        TxnData::BlockMetadata(tx_inner) => &tx_inner.events,
+        TxnData::Genesis(tx_inner) => &tx_inner.events,
+        TxnData::User(tx_inner) => &tx_inner.events,
+        _ => &default,
+    };
+    let request_default = None;
+    let tnx_user_request = match txn_data {
+        TxnData::User(tx_inner) => &tx_inner.request,
+        _ => &request_default,
+    };
+    // User transactions need a request. Genesis and block metadata events are stored without one
+    if matches!(txn_data, TxnData::User(_)) && tnx_user_request.is_none() {
+        return;
+    }
+    // Genesis and some system transactions may be missing info or timestamp, skip them
+    let transaction_info = match (txn.info.as_ref(), txn.timestamp.as_ref()) {
+        (Some(info), Some(_)) => info,
+        _ => {
+            tracing::warn!(
+                transaction_version = txn_version,
+                "Transaction info or timestamp doesn't exist"
+            );
+            PROCESSOR_UNKNOWN_TYPE_COUNT
+                .with_label_values(&["EventsProcessor"])
+                .inc();
+            return;
+        },
+    };
+    let inserted_at = &txn.timestamp;
+
+    if let (TxnData::User(_), Some(request)) = (txn_data, tnx_user_request.as_ref()) {
+        // Only the multisig rows below use the sender, the raw events are checked individually
+        let sender = match try_standardize_address(request.sender.as_str()) {
+            Ok(sender) => Some(sender),
+            Err(e) => {
+                tracing::warn!(
+                    transaction_version = txn_version,
+                    error = ?e,
+                    "Invalid sender address, skipping multisig account events"
+                );
+                None
+            },
+        };
+        let multisig_resources = transaction_info
+            .changes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, c)| match c.change.as_ref() {
+                Some(Change::WriteResource(write_resource)) => Some(write_resource),
+                Some(_) => None,
+                None => {
+                    tracing::warn!(
+                        transaction_version = txn_version,
+                        change_index = index,
+                        "Write set change is empty, skipping"
+                    );
+                    None
+                },
+            })
+            .filter(|write_resource| {
+                write_resource.type_str.as_str() == "0x1::multisig_account::MultisigAccount"
+            });
+        if let Some(sender) = sender {
+            for write_resource in multisig_resources {
+                let event = Event {
+                    key: Some(EventKey {
+                        account_address: sender.clone(),
+                        creation_number: request.sequence_number,
+                    }),
+                    sequence_number: request.sequence_number,
+                    r#type: None,
+                    type_str: write_resource.type_str.to_string(),
+                    data: write_resource.data.to_string(),
+                };
+                let txn_create_multisig_event = EventModel::from_event(
+                    &event,
+                    txn_version,
+                    block_height,
+                    epoch,
+                    events.len() as i64,
+                    tnx_user_request,
+                    inserted_at,
+                );
+                events.push(txn_create_multisig_event);
+            }
+        }
+    }
+    let txn_events = EventModel::from_events(
+        raw_events,
+        txn_version,
+        block_height,
+        epoch,
+        tnx_user_request,
+        inserted_at,
+    );
+    events.extend(txn_events.into_iter().filter(|txn_event| {
+        if let Err(e) = try_standardize_address(&txn_event.account_address) {
+            tracing::warn!(
+                transaction_version = txn_version,
+                event_index = txn_event.event_index,
+                error = ?e,
+                "Invalid event account address, skipping event"
+            );
+            return false;
+        }
+        event_filter.should_store(txn_event)
+    }));
+}
+
 #[async_trait]
 impl ProcessorTrait for EventsProcessor {
     fn name(&self) -> &'static str {
@@ -158,93 +402,9 @@ impl ProcessorTrait for EventsProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
-
-        let mut events = vec![];
-        for txn in &transactions {
-            let txn_version = txn.version as i64;
-            let block_height = txn.block_height as i64;
-            let txn_data = match txn.txn_data.as_ref() {
-                Some(data) => data,
-                None => {
-                    tracing::warn!(
-                        transaction_version = txn_version,
-                        "Transaction data doesn't exist"
-                    );
-                    PROCESSOR_UNKNOWN_TYPE_COUNT
-                        .with_label_values(&["EventsProcessor"])
-                        .inc();
-                    continue;
-                },
-            };
-
-            let default = vec![];
-            let raw_events = match txn_data {
-                TxnData::BlockMetadata(tx_inner) => &tx_inner.events,
-                TxnData::Genesis(tx_inner) => &tx_inner.events,
-                TxnData::User(tx_inner) => &tx_inner.events,
-                _ => &default,
-            };
-            let request_default = None;
-            let tnx_user_request = match txn_data {
-                TxnData::User(tx_inner) => &tx_inner.request,
-                _ => &request_default,
-            };
-            //  If request is None, it means that the transaction is not a user transaction, skip
-            if tnx_user_request.is_none() {
-                continue;
-            }
-            let inserted_at = txn.timestamp.clone();
-
-            if let TxnData::User(txn_inner) = txn_data {
-                let changes = &txn.clone().info.unwrap().changes;
-                let filtered = changes.iter().filter(|c| {
-                    let Change::WriteResource(write_resource) = &c.change.as_ref().unwrap() else {
-                        return false;
-                    };
-                    write_resource.type_str.as_str() == "0x1::multisig_account::MultisigAccount"
-                });
-                filtered.for_each(|c| {
-                    if let Change::WriteResource(write_resource) = &c.change.as_ref().unwrap() {
-                        let from = tnx_user_request.as_ref().unwrap().sender.as_str();
-                        let event = Event {
-                            key: Some(EventKey {
-                                account_address: standardize_address(from),
-                                creation_number: txn_inner.clone().request.unwrap().sequence_number,
-                            }),
-                            sequence_number: txn_inner.clone().request.unwrap().sequence_number,
-                            r#type: None,
-                            type_str: write_resource.type_str.to_string(),
-                            data: write_resource.data.to_string(),
-                        };
-                        let txn_create_multisig_event = EventModel::from_event(
-                            &event,
-                            txn_version,
-                            block_height,
-                            events.len() as i64,
-                            tnx_user_request,
-                            &inserted_at,
-                        );
-                        events.push(txn_create_multisig_event);
-                    }
-                });
-            }
-            let txn_events = EventModel::from_events(
-                raw_events,
-                txn_version,
-                block_height,
-                tnx_user_request,
-                &inserted_at,
-            );
-            for txn_event in txn_events {
-                if (!FILTERED_EVENTS.contains(&txn_event.type_.as_str())
-                    || REQUIRED_EVENTS.contains(&txn_event.type_.as_str()))
-                    && !FILTERED_EVENTS.contains(&txn_event.entry_function_id_str.as_str())
-                {
-                    events.push(txn_event);
-                }
-            }
-        }
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
+
+        let events = parse_transactions(&transactions, &self.event_filter);
 
         let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
         let db_insertion_start = std::time::Instant::now();
@@ -256,6 +416,7 @@ impl ProcessorTrait for EventsProcessor {
             end_version,
             &events,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -284,4 +445,420 @@ impl ProcessorTrait for EventsProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![TableFieldCount::new::<EventModel>("events", &[])]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::util::standardize_address;
+    use aptos_protos::transaction::v1::{
+        transaction_payload::Payload, EntryFunctionPayload, TransactionInfo, TransactionPayload,
+        UserTransaction, UserTransactionRequest, WriteResource,
+    };
+
+    fn event_filter(filters: &[&str], mode: &str) -> EventFilter {
+        let config: EventsProcessorConfig = serde_json::from_value(serde_json::json!({
+            "event_filters": filters,
+            "event_filter_mode": mode,
+        }))
+        .unwrap();
+        EventFilter::new(&config).unwrap()
+    }
+
+    /// Collects formatted logs so tests can inspect them
+    #[derive(Clone, Default)]
+    struct LogBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> tracing_subscriber::fmt::MakeWriter<'a> for LogBuffer {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_empty_batch() {
+        use crate::utils::database::MyDbConnection;
+        use diesel_async::pooled_connection::{bb8::Pool, AsyncDieselConnectionManager};
+
+        // An empty batch never touches the database, so nothing needs to listen on this port
+        let manager = AsyncDieselConnectionManager::<MyDbConnection>::new(
+            "postgres://postgres@127.0.0.1:1/postgres",
+        );
+        let pool = std::sync::Arc::new(Pool::builder().build_unchecked(manager));
+        let processor = EventsProcessor::new(
+            pool.clone(),
+            EventsProcessorConfig::default(),
+            AHashMap::new(),
+            DbWriteConfig::default(),
+        )
+        .unwrap();
+
+        let result = processor.process_transactions(vec![], 1, 1, None).await;
+        assert!(result.is_ok());
+        assert_eq!(pool.state().connections, 0);
+    }
+
+    #[test]
+    fn test_transaction_logs_have_version_span() {
+        let logs = LogBuffer::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let txn = Transaction {
+            version: 42,
+            ..Transaction::default()
+        };
+        let events = tracing::subscriber::with_default(subscriber, || {
+            parse_transactions(&[txn], &EventFilter::default())
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("txn{version=42}"), "{}", logs);
+        assert!(logs.contains("Transaction data doesn't exist"), "{}", logs);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_transaction_without_info_is_skipped() {
+        let logs = LogBuffer::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(logs.clone())
+            .with_ansi(false)
+            .finish();
+        let txn = Transaction {
+            version: 11,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            info: None,
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "0xa".to_string(),
+                    ..Default::default()
+                }),
+                events: vec![Event {
+                    key: Some(EventKey {
+                        creation_number: 0,
+                        account_address: "0xa".to_string(),
+                    }),
+                    type_str: "0x1::coin::DepositEvent".to_string(),
+                    data: "{}".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })),
+            ..Transaction::default()
+        };
+        let skipped = PROCESSOR_UNKNOWN_TYPE_COUNT.with_label_values(&["EventsProcessor"]);
+        let skipped_before = skipped.get();
+
+        let events = tracing::subscriber::with_default(subscriber, || {
+            parse_transactions(&[txn], &EventFilter::default())
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(
+            logs.contains("Transaction info or timestamp doesn't exist"),
+            "{}",
+            logs
+        );
+        assert!(events.is_empty());
+        // Other tests may skip transactions concurrently, so only check it went up
+        assert!(skipped.get() > skipped_before);
+    }
+
+    #[test]
+    fn test_block_metadata_events_are_stored() {
+        let txn = Transaction {
+            version: 7,
+            block_height: 3,
+            epoch: 2,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            info: Some(Default::default()),
+            txn_data: Some(TxnData::BlockMetadata(
+                aptos_protos::transaction::v1::BlockMetadataTransaction {
+                    events: vec![Event {
+                        key: Some(EventKey {
+                            creation_number: 3,
+                            account_address: "0x1".to_string(),
+                        }),
+                        sequence_number: 10,
+                        r#type: None,
+                        type_str: "0x1::block::NewBlockEvent".to_string(),
+                        data: r#"{"epoch": "1", "round": "2"}"#.to_string(),
+                    }],
+                    ..Default::default()
+                },
+            )),
+            ..Transaction::default()
+        };
+
+        let events = parse_transactions(&[txn], &EventFilter::default());
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.transaction_version, 7);
+        assert_eq!(event.transaction_block_height, 3);
+        assert_eq!(event.epoch, Some(2));
+        assert_eq!(event.type_, "0x1::block::NewBlockEvent");
+        assert_eq!(event.event_name, "NewBlockEvent");
+        assert_eq!(event.from, "");
+        assert_eq!(event.entry_function_payload, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_empty_write_set_change_is_skipped() {
+        let multisig_resource = WriteSetChange {
+            change: Some(Change::WriteResource(WriteResource {
+                address: "0xa".to_string(),
+                type_str: "0x1::multisig_account::MultisigAccount".to_string(),
+                data: r#"{"num_signatures_required": "1"}"#.to_string(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let txn = Transaction {
+            version: 9,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            info: Some(TransactionInfo {
+                changes: vec![WriteSetChange::default(), multisig_resource],
+                ..Default::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "0xa".to_string(),
+                    sequence_number: 4,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })),
+            ..Transaction::default()
+        };
+
+        let events = parse_transactions(&[txn], &EventFilter::default());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].type_, "0x1::multisig_account::MultisigAccount");
+        assert_eq!(events[0].from, "0xa");
+    }
+
+    #[test]
+    fn test_invalid_sender_keeps_raw_events() {
+        let multisig_resource = WriteSetChange {
+            change: Some(Change::WriteResource(WriteResource {
+                address: "0xa".to_string(),
+                type_str: "0x1::multisig_account::MultisigAccount".to_string(),
+                data: r#"{"num_signatures_required": "1"}"#.to_string(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let txn = Transaction {
+            version: 10,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            info: Some(TransactionInfo {
+                changes: vec![multisig_resource],
+                ..Default::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "not an address".to_string(),
+                    ..Default::default()
+                }),
+                events: vec![Event {
+                    key: Some(EventKey {
+                        creation_number: 0,
+                        account_address: "0xa".to_string(),
+                    }),
+                    type_str: "0x1::coin::DepositEvent".to_string(),
+                    data: "{}".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })),
+            ..Transaction::default()
+        };
+
+        // Only the multisig row built from the sender is skipped
+        let events = parse_transactions(&[txn], &EventFilter::default());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].type_, "0x1::coin::DepositEvent");
+    }
+
+    #[test]
+    fn test_empty_event_filters_match_everything() {
+        let filter = EventFilter::new(&EventsProcessorConfig::default()).unwrap();
+        assert!(filter.matches("0x1::coin::DepositEvent"));
+        assert!(filter.matches("0x1::transaction_fee::FeeStatement"));
+    }
+
+    #[test]
+    fn test_contains_event_filters() {
+        let filter = event_filter(
+            &["0x1::multisig_account", "0x1::voting::CreateProposalEvent"],
+            "contains",
+        );
+        assert!(filter.matches("0x1::multisig_account::VoteEvent"));
+        assert!(filter.matches("0x1::voting::CreateProposalEvent"));
+        assert!(!filter.matches("0x1::voting::VoteEvent"));
+        assert!(!filter.matches("0x1::coin::DepositEvent"));
+    }
+
+    #[test]
+    fn test_exact_event_filters() {
+        let filter = event_filter(&["0x1::multisig_account::VoteEvent"], "exact");
+        assert!(filter.matches("0x1::multisig_account::VoteEvent"));
+        assert!(!filter.matches("0x1::multisig_account::VoteEventV2"));
+        assert!(!filter.matches("0x1::multisig_account"));
+    }
+
+    #[test]
+    fn test_regex_event_filters() {
+        let filter = event_filter(&[r"^0x1::[a-z_]+::VoteEvent$"], "regex");
+        assert!(filter.matches("0x1::multisig_account::VoteEvent"));
+        assert!(filter.matches("0x1::voting::VoteEvent"));
+        assert!(!filter.matches("0x1::multisig_account::CreateTransactionEvent"));
+        assert!(!filter.matches("0x2::voting::VoteEvent"));
+    }
+
+    #[test]
+    fn test_invalid_regex_event_filter() {
+        let config = EventsProcessorConfig {
+            event_filters: vec!["(".to_string()],
+            event_filter_mode: EventFilterMode::Regex,
+            ..EventsProcessorConfig::default()
+        };
+        assert!(EventFilter::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_account_prefix_event_filters() {
+        let config = EventsProcessorConfig {
+            account_prefixes: vec!["0xa".to_string()],
+            ..EventsProcessorConfig::default()
+        };
+        let filter = EventFilter::new(&config).unwrap();
+        assert!(filter.matches("0xa::marketplace::ListEvent"));
+        assert!(filter.matches(&format!(
+            "{}::marketplace::ListEvent",
+            standardize_address("0xa")
+        )));
+        assert!(filter.matches("0xa::coin::Deposit<0x1::aptos_coin::AptosCoin>"));
+        assert!(!filter.matches("0x1::coin::DepositEvent"));
+        assert!(!filter.matches("0xab::marketplace::ListEvent"));
+        assert!(!filter.matches("vector<u8>"));
+
+        // Matched with OR semantics alongside the type filters
+        let config = EventsProcessorConfig {
+            event_filters: vec!["0x1::coin::DepositEvent".to_string()],
+            event_filter_mode: EventFilterMode::Exact,
+            account_prefixes: vec!["0xa::".to_string()],
+            ..EventsProcessorConfig::default()
+        };
+        let filter = EventFilter::new(&config).unwrap();
+        assert!(filter.matches("0xa::marketplace::ListEvent"));
+        assert!(filter.matches("0x1::coin::DepositEvent"));
+        assert!(!filter.matches("0x1::coin::WithdrawEvent"));
+    }
+
+    #[test]
+    fn test_excluded_events_are_skipped() {
+        let user_txn = |version: u64, entry_function: &str, event_types: &[&str]| Transaction {
+            version,
+            timestamp: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            info: Some(Default::default()),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: "0xa".to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(Payload::EntryFunctionPayload(EntryFunctionPayload {
+                            entry_function_id_str: entry_function.to_string(),
+                            ..Default::default()
+                        })),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: event_types
+                    .iter()
+                    .map(|event_type| Event {
+                        key: Some(EventKey {
+                            creation_number: 0,
+                            account_address: "0xa".to_string(),
+                        }),
+                        type_str: event_type.to_string(),
+                        data: "{}".to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            })),
+            ..Transaction::default()
+        };
+        let transactions = [
+            user_txn(1, "0x1::multisig_account::create_with_owners", &[
+                "0x1::coin::DepositEvent",
+            ]),
+            user_txn(2, "0x1::aptos_account::transfer", &[
+                "0x1::coin::DepositEvent",
+                "0x1::transaction_fee::FeeStatement",
+            ]),
+        ];
+        let config = EventsProcessorConfig {
+            exclude_event_types: vec!["0x1::transaction_fee::FeeStatement".to_string()],
+            exclude_entry_functions: vec!["0x1::multisig_account::create_with_owners".to_string()],
+            ..EventsProcessorConfig::default()
+        };
+
+        let events = parse_transactions(&transactions, &EventFilter::new(&config).unwrap());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].transaction_version, 2);
+        assert_eq!(events[0].type_, "0x1::coin::DepositEvent");
+
+        // Nothing is excluded by default
+        let events = parse_transactions(&transactions, &EventFilter::default());
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_account_prefix() {
+        let config = EventsProcessorConfig {
+            account_prefixes: vec!["0x1::coin".to_string()],
+            ..EventsProcessorConfig::default()
+        };
+        assert!(EventFilter::new(&config).is_err());
+    }
 }
@@ -21,7 +21,10 @@ use crate::{
     schema,
     utils::{
         counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
         util::{get_entry_function_from_user_request, standardize_address},
     },
 };
@@ -43,13 +46,19 @@ pub const APTOS_COIN_TYPE_STR: &str = "0x1::aptos_coin::AptosCoin";
 pub struct FungibleAssetProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl FungibleAssetProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -75,6 +84,7 @@ async fn insert_to_db(
     fungible_asset_balances: &[FungibleAssetBalance],
     current_fungible_asset_balances: &[CurrentFungibleAssetBalance],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -91,6 +101,7 @@ async fn insert_to_db(
             "fungible_asset_activities",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let fam = execute_in_chunks(
         conn.clone(),
@@ -100,6 +111,7 @@ async fn insert_to_db(
             "fungible_asset_metadata",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let fab = execute_in_chunks(
         conn.clone(),
@@ -109,6 +121,7 @@ async fn insert_to_db(
             "fungible_asset_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cfab = execute_in_chunks(
         conn,
@@ -118,6 +131,7 @@ async fn insert_to_db(
             "current_fungible_asset_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let (faa_res, fam_res, fab_res, cfab_res) = tokio::join!(faa, fam, fab, cfab);
     for res in [faa_res, fam_res, fab_res, cfab_res] {
@@ -241,7 +255,7 @@ impl ProcessorTrait for FungibleAssetProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let (
             fungible_asset_activities,
@@ -263,6 +277,7 @@ impl ProcessorTrait for FungibleAssetProcessor {
             &fungible_asset_balances,
             &current_fungible_asset_balances,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
         let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
@@ -290,6 +305,28 @@ impl ProcessorTrait for FungibleAssetProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<FungibleAssetActivity>("fungible_asset_activities", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<FungibleAssetMetadataModel>("fungible_asset_metadata", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<FungibleAssetBalance>("fungible_asset_balances", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<CurrentFungibleAssetBalance>(
+                "current_fungible_asset_balances",
+                &["inserted_at"],
+            ),
+        ]
+    }
 }
 
 /// V2 coin is called fungible assets and this flow includes all data from V1 in coin_processor
@@ -27,7 +27,7 @@ use self::{
     ans_processor::{AnsProcessor, AnsProcessorConfig},
     coin_processor::CoinProcessor,
     default_processor::DefaultProcessor,
-    events_processor::EventsProcessor,
+    events_processor::{EventsProcessor, EventsProcessorConfig},
     fungible_asset_processor::FungibleAssetProcessor,
     monitoring_processor::MonitoringProcessor,
     nft_metadata_processor::{NftMetadataProcessor, NftMetadataProcessorConfig},
@@ -39,11 +39,14 @@ use self::{
     user_transaction_processor::UserTransactionProcessor,
 };
 use crate::{
-    models::processor_status::ProcessorStatus,
+    models::processor_status::{processor_lag_in_secs, ProcessorStatus},
     schema::processor_status,
     utils::{
-        counters::{GOT_CONNECTION_COUNT, UNABLE_TO_GET_CONNECTION_COUNT},
-        database::{execute_with_better_error, PgDbPool, PgPoolConnection},
+        counters::{GOT_CONNECTION_COUNT, PROCESSOR_LAG_IN_SECS, UNABLE_TO_GET_CONNECTION_COUNT},
+        database::{
+            check_table_field_count, execute_with_better_error, DbWriteConfig, PgDbPool, PgPool,
+            PgPoolConnection, TableFieldCount,
+        },
         util::parse_timestamp,
     },
 };
@@ -51,8 +54,16 @@ use aptos_protos::transaction::v1::Transaction as ProtoTransaction;
 use async_trait::async_trait;
 use diesel::{pg::upsert::excluded, ExpressionMethods};
 use enum_dispatch::enum_dispatch;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    time::{Duration, Instant},
+};
+
+// Bounds of the backoff between attempts to get a DB connection
+const GET_CONN_BASE_BACKOFF_MS: u64 = 50;
+const GET_CONN_MAX_BACKOFF_MS: u64 = 5_000;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ProcessingResult {
@@ -82,8 +93,24 @@ pub trait ProcessorTrait: Send + Sync + Debug {
     /// This is used by the `get_conn()` helper below
     fn connection_pool(&self) -> &PgDbPool;
 
+    /// Gets how this processor writes to the database, e.g. whether it's a dry run
+    fn db_write_config(&self) -> DbWriteConfig;
+
+    /// Tables this processor inserts into, with the field count of the model inserted into each.
+    /// These are checked against the database at startup.
+    fn table_field_counts(&self) -> Vec<TableFieldCount>;
+
     //* Below are helper methods that don't need to be implemented *//
 
+    /// Fails if any model from `table_field_counts` doesn't fit the columns of its table
+    async fn check_table_field_counts(&self) -> anyhow::Result<()> {
+        let mut conn = self.get_conn().await;
+        for table in self.table_field_counts() {
+            check_table_field_count(&mut conn, &table).await?;
+        }
+        Ok(())
+    }
+
     /// Gets an instance of the connection pool
     fn get_pool(&self) -> PgDbPool {
         let pool = self.connection_pool();
@@ -91,27 +118,22 @@ pub trait ProcessorTrait: Send + Sync + Debug {
     }
 
     /// Gets the connection.
-    /// If it was unable to do so (default timeout: 30s), it will keep retrying until it can.
+    /// If it was unable to do so (default timeout: 30s), it will keep retrying with backoff until it can.
     async fn get_conn(&self) -> PgPoolConnection {
-        let pool = self.connection_pool();
-        loop {
-            match pool.get().await {
-                Ok(conn) => {
-                    GOT_CONNECTION_COUNT.inc();
-                    return conn;
-                },
-                Err(err) => {
-                    UNABLE_TO_GET_CONNECTION_COUNT.inc();
-                    tracing::error!(
-                        // todo bb8 doesn't let you read the connection timeout.
-                        //"Could not get DB connection from pool, will retry in {:?}. Err: {:?}",
-                        //pool.connection_timeout(),
-                        "Could not get DB connection from pool, will retry. Err: {:?}",
-                        err
-                    );
-                },
-            };
-        }
+        self.get_conn_with_retries(None, None)
+            .await
+            .expect("Getting a connection without a retry limit can't fail")
+    }
+
+    /// Gets the connection, retrying with exponential backoff and jitter between attempts.
+    /// Gives up after `max_retries` failed retries or once `max_duration` has passed,
+    /// whichever comes first, or retries forever if both are None.
+    async fn get_conn_with_retries(
+        &self,
+        max_retries: Option<u32>,
+        max_duration: Option<Duration>,
+    ) -> anyhow::Result<PgPoolConnection> {
+        get_conn_from_pool(self.connection_pool(), max_retries, max_duration).await
     }
 
     /// Store last processed version from database. We can assume that all previously processed
@@ -121,6 +143,9 @@ pub trait ProcessorTrait: Send + Sync + Debug {
         version: u64,
         last_transaction_timestamp: Option<aptos_protos::util::timestamp::Timestamp>,
     ) -> anyhow::Result<()> {
+        if self.db_write_config().dry_run {
+            return Ok(());
+        }
         let timestamp = last_transaction_timestamp.map(|t| parse_timestamp(&t, version as i64));
         let status = ProcessorStatus {
             processor: self.name().to_string(),
@@ -143,10 +168,69 @@ pub trait ProcessorTrait: Send + Sync + Debug {
             Some(" WHERE processor_status.last_success_version <= EXCLUDED.last_success_version "),
         )
         .await?;
+        if let Some(lag_in_secs) = processor_lag_in_secs(
+            chrono::Utc::now().naive_utc(),
+            status.last_transaction_timestamp,
+        ) {
+            PROCESSOR_LAG_IN_SECS
+                .with_label_values(&[self.name()])
+                .set(lag_in_secs);
+        }
         Ok(())
     }
 }
 
+async fn get_conn_from_pool(
+    pool: &PgPool,
+    max_retries: Option<u32>,
+    max_duration: Option<Duration>,
+) -> anyhow::Result<PgPoolConnection> {
+    let deadline = max_duration.map(|max_duration| Instant::now() + max_duration);
+    let mut retries: u32 = 0;
+    loop {
+        match pool.get().await {
+            Ok(conn) => {
+                GOT_CONNECTION_COUNT.inc();
+                return Ok(conn);
+            },
+            Err(err) => {
+                UNABLE_TO_GET_CONNECTION_COUNT.inc();
+                let now = Instant::now();
+                if max_retries.is_some_and(|max_retries| retries >= max_retries)
+                    || deadline.is_some_and(|deadline| now >= deadline)
+                {
+                    anyhow::bail!(
+                        "Could not get DB connection from pool after {} retries. Err: {:?}",
+                        retries,
+                        err
+                    );
+                }
+                retries += 1;
+                let mut backoff = get_conn_backoff(retries);
+                if let Some(deadline) = deadline {
+                    backoff = backoff.min(deadline - now);
+                }
+                tracing::error!(
+                    // todo bb8 doesn't let you read the connection timeout.
+                    "Could not get DB connection from pool, will retry in {:?}. Err: {:?}",
+                    backoff,
+                    err
+                );
+                tokio::time::sleep(backoff).await;
+            },
+        };
+    }
+}
+
+/// Exponential backoff with full jitter for the given retry number, capped at
+/// GET_CONN_MAX_BACKOFF_MS so a dead database doesn't spin the CPU.
+fn get_conn_backoff(retry: u32) -> Duration {
+    let max_backoff_ms = GET_CONN_BASE_BACKOFF_MS
+        .saturating_mul(1 << retry.min(16))
+        .min(GET_CONN_MAX_BACKOFF_MS);
+    Duration::from_millis(rand::thread_rng().gen_range(0..=max_backoff_ms))
+}
+
 /// This enum captures the configs for all the different processors that are defined.
 /// The configs for each processor should only contain configuration specific to that
 /// processor. For configuration that is common to all processors, put it in
@@ -183,7 +267,7 @@ pub enum ProcessorConfig {
     AnsProcessor(AnsProcessorConfig),
     CoinProcessor,
     DefaultProcessor,
-    EventsProcessor,
+    EventsProcessor(EventsProcessorConfig),
     FungibleAssetProcessor,
     MonitoringProcessor,
     NftMetadataProcessor(NftMetadataProcessorConfig),
@@ -249,4 +333,32 @@ mod test {
     fn test_processor_names_complete() {
         assert_eq!(ProcessorName::VARIANTS, ProcessorDiscriminants::VARIANTS);
     }
+
+    #[tokio::test]
+    async fn test_get_conn_gives_up() {
+        use crate::utils::database::MyDbConnection;
+        use diesel_async::pooled_connection::{bb8::Pool, AsyncDieselConnectionManager};
+
+        // Nothing listens on this port, so the pool never yields a connection
+        let manager = AsyncDieselConnectionManager::<MyDbConnection>::new(
+            "postgres://postgres@127.0.0.1:1/postgres",
+        );
+        let pool = Pool::builder()
+            .connection_timeout(Duration::from_millis(50))
+            .build_unchecked(manager);
+
+        let start = Instant::now();
+        assert!(get_conn_from_pool(&pool, Some(2), None).await.is_err());
+        // 3 attempts plus at most 100ms and 200ms of backoff
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        let start = Instant::now();
+        assert!(
+            get_conn_from_pool(&pool, None, Some(Duration::from_millis(300)))
+                .await
+                .is_err()
+        );
+        // The last attempt may start just before the deadline
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
 }
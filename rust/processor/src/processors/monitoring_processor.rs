@@ -2,18 +2,22 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{ProcessingResult, ProcessorName, ProcessorTrait};
-use crate::utils::database::PgDbPool;
+use crate::utils::database::{DbWriteConfig, PgDbPool, TableFieldCount};
 use aptos_protos::transaction::v1::Transaction;
 use async_trait::async_trait;
 use std::fmt::Debug;
 
 pub struct MonitoringProcessor {
     connection_pool: PgDbPool,
+    db_write_config: DbWriteConfig,
 }
 
 impl MonitoringProcessor {
-    pub fn new(connection_pool: PgDbPool) -> Self {
-        Self { connection_pool }
+    pub fn new(connection_pool: PgDbPool, db_write_config: DbWriteConfig) -> Self {
+        Self {
+            connection_pool,
+            db_write_config,
+        }
     }
 }
 
@@ -46,11 +50,19 @@ impl ProcessorTrait for MonitoringProcessor {
             end_version,
             processing_duration_in_secs: 0.0,
             db_insertion_duration_in_secs: 0.0,
-            last_transaction_timestamp: transactions.last().unwrap().timestamp.clone(),
+            last_transaction_timestamp: transactions.last().and_then(|t| t.timestamp.clone()),
         })
     }
 
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![]
+    }
 }
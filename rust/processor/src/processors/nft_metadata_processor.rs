@@ -14,7 +14,7 @@ use crate::{
         },
     },
     utils::{
-        database::{PgDbPool, PgPoolConnection},
+        database::{DbWriteConfig, PgDbPool, PgPoolConnection, TableFieldCount},
         util::{parse_timestamp, remove_null_bytes, standardize_address},
     },
     IndexerGrpcProcessorConfig,
@@ -49,10 +49,15 @@ pub struct NftMetadataProcessor {
     connection_pool: PgDbPool,
     chain_id: u8,
     config: NftMetadataProcessorConfig,
+    db_write_config: DbWriteConfig,
 }
 
 impl NftMetadataProcessor {
-    pub fn new(connection_pool: PgDbPool, config: NftMetadataProcessorConfig) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        config: NftMetadataProcessorConfig,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         tracing::info!("init NftMetadataProcessor");
 
         // Crate reads from authentication from file specified in
@@ -65,6 +70,7 @@ impl NftMetadataProcessor {
             connection_pool,
             chain_id: 0,
             config,
+            db_write_config,
         }
     }
 
@@ -98,7 +104,7 @@ impl ProcessorTrait for NftMetadataProcessor {
         db_chain_id: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut conn = self.get_conn().await;
         let query_retries = self.config.query_retries;
@@ -114,11 +120,6 @@ impl ProcessorTrait for NftMetadataProcessor {
         let table_handle_to_owner =
             TableMetadataForToken::get_table_handle_to_owner_from_transactions(&transactions);
 
-        // Initialize pubsub client
-        let config = ClientConfig::default().with_auth().await?;
-        let client = Client::new(config).await?;
-        let topic = client.topic(&self.config.pubsub_topic_name.clone());
-        let publisher = topic.new_publisher(None);
         let ordering_key = get_current_timestamp();
 
         // Publish CurrentTokenDataV2 and CurrentCollectionV2 from transactions
@@ -151,6 +152,23 @@ impl ProcessorTrait for NftMetadataProcessor {
         }
 
         let processing_duration_in_secs = processing_start.elapsed().as_secs_f64();
+
+        if self.db_write_config.dry_run {
+            info!(
+                start_version = start_version,
+                end_version = end_version,
+                num_messages = pubsub_messages.len(),
+                "[NFT Metadata Crawler] Dry run, skipping publishing to queue"
+            );
+            return Ok(ProcessingResult {
+                start_version,
+                end_version,
+                processing_duration_in_secs,
+                db_insertion_duration_in_secs: 0.0,
+                last_transaction_timestamp,
+            });
+        }
+
         let db_insertion_start = std::time::Instant::now();
 
         info!(
@@ -159,6 +177,12 @@ impl ProcessorTrait for NftMetadataProcessor {
             "[NFT Metadata Crawler] Publishing to queue"
         );
 
+        // Initialize pubsub client
+        let config = ClientConfig::default().with_auth().await?;
+        let client = Client::new(config).await?;
+        let topic = client.topic(&self.config.pubsub_topic_name.clone());
+        let publisher = topic.new_publisher(None);
+
         let chunks: Vec<Vec<PubsubMessage>> = pubsub_messages
             .chunks(CHUNK_SIZE)
             .map(|chunk| chunk.to_vec())
@@ -189,6 +213,14 @@ impl ProcessorTrait for NftMetadataProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![]
+    }
 }
 
 fn clean_token_pubsub_message(ctd: CurrentTokenDataV2, db_chain_id: u64) -> String {
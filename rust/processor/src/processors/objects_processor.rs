@@ -9,7 +9,10 @@ use crate::{
     },
     schema,
     utils::{
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
         util::standardize_address,
     },
     IndexerGrpcProcessorConfig,
@@ -39,6 +42,7 @@ pub struct ObjectsProcessor {
     connection_pool: PgDbPool,
     config: ObjectsProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl ObjectsProcessor {
@@ -46,11 +50,13 @@ impl ObjectsProcessor {
         connection_pool: PgDbPool,
         config: ObjectsProcessorConfig,
         per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
     ) -> Self {
         Self {
             connection_pool,
             config,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -73,6 +79,7 @@ async fn insert_to_db(
     end_version: u64,
     (objects, current_objects): (&[Object], &[CurrentObject]),
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -86,12 +93,14 @@ async fn insert_to_db(
         insert_objects_query,
         objects,
         get_config_table_chunk_size::<Object>("objects", per_table_chunk_sizes),
+        db_write_config,
     );
     let co = execute_in_chunks(
         conn,
         insert_current_objects_query,
         current_objects,
         get_config_table_chunk_size::<CurrentObject>("current_objects", per_table_chunk_sizes),
+        db_write_config,
     );
     let (io_res, co_res) = tokio::join!(io, co);
     for res in [io_res, co_res] {
@@ -159,7 +168,7 @@ impl ProcessorTrait for ObjectsProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut conn = self.get_conn().await;
         let query_retries = self.config.query_retries;
@@ -270,6 +279,7 @@ impl ProcessorTrait for ObjectsProcessor {
             end_version,
             (&all_objects, &all_current_objects),
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
         let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
@@ -298,4 +308,15 @@ impl ProcessorTrait for ObjectsProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<Object>("objects", &["inserted_at"]),
+            TableFieldCount::new::<CurrentObject>("current_objects", &["inserted_at"]),
+        ]
+    }
 }
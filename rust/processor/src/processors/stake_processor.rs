@@ -18,7 +18,10 @@ use crate::{
     },
     schema,
     utils::{
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
         util::{parse_timestamp, standardize_address},
     },
     IndexerGrpcProcessorConfig,
@@ -49,6 +52,7 @@ pub struct StakeProcessor {
     connection_pool: PgDbPool,
     config: StakeProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl StakeProcessor {
@@ -56,11 +60,13 @@ impl StakeProcessor {
         connection_pool: PgDbPool,
         config: StakeProcessorConfig,
         per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
     ) -> Self {
         Self {
             connection_pool,
             config,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -91,6 +97,7 @@ async fn insert_to_db(
     current_delegator_pool_balances: &[CurrentDelegatorPoolBalance],
     current_delegated_voter: &[CurrentDelegatedVoter],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -107,12 +114,14 @@ async fn insert_to_db(
             "current_staking_pool_voter",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let pv = execute_in_chunks(
         conn.clone(),
         insert_proposal_votes_query,
         proposal_votes,
         get_config_table_chunk_size::<ProposalVote>("proposal_votes", per_table_chunk_sizes),
+        db_write_config,
     );
     let da = execute_in_chunks(
         conn.clone(),
@@ -122,6 +131,7 @@ async fn insert_to_db(
             "delegated_staking_activities",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let db = execute_in_chunks(
         conn.clone(),
@@ -131,6 +141,7 @@ async fn insert_to_db(
             "delegator_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cdb = execute_in_chunks(
         conn.clone(),
@@ -140,6 +151,7 @@ async fn insert_to_db(
             "current_delegator_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let dp = execute_in_chunks(
         conn.clone(),
@@ -149,6 +161,7 @@ async fn insert_to_db(
             "delegated_staking_pools",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let dpb = execute_in_chunks(
         conn.clone(),
@@ -158,6 +171,7 @@ async fn insert_to_db(
             "delegated_staking_pool_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cdpb = execute_in_chunks(
         conn.clone(),
@@ -167,6 +181,7 @@ async fn insert_to_db(
             "current_delegated_staking_pool_balances",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cdv = execute_in_chunks(
         conn,
@@ -176,6 +191,7 @@ async fn insert_to_db(
             "current_delegated_voter",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
 
     let (cspv_res, pv_res, da_res, db_res, cdb_res, dp_res, dpb_res, cdpb_res, cdv_res) =
@@ -395,7 +411,7 @@ impl ProcessorTrait for StakeProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut conn = self.get_conn().await;
         let query_retries = self.config.query_retries;
@@ -574,6 +590,7 @@ impl ProcessorTrait for StakeProcessor {
             &all_current_delegator_pool_balances,
             &all_current_delegated_voter,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
         let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
@@ -601,4 +618,35 @@ impl ProcessorTrait for StakeProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<CurrentStakingPoolVoter>("current_staking_pool_voter", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<ProposalVote>("proposal_votes", &["inserted_at"]),
+            TableFieldCount::new::<DelegatedStakingActivity>("delegated_staking_activities", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<DelegatorBalance>("delegator_balances", &["inserted_at"]),
+            TableFieldCount::new::<CurrentDelegatorBalance>("current_delegator_balances", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<DelegatorPool>("delegated_staking_pools", &["inserted_at"]),
+            TableFieldCount::new::<DelegatorPoolBalance>("delegated_staking_pool_balances", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<CurrentDelegatorPoolBalance>(
+                "current_delegated_staking_pool_balances",
+                &["inserted_at"],
+            ),
+            TableFieldCount::new::<CurrentDelegatedVoter>("current_delegated_voter", &[
+                "inserted_at",
+            ]),
+        ]
+    }
 }
@@ -16,7 +16,9 @@ use crate::{
         },
     },
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+    utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool, TableFieldCount,
+    },
     IndexerGrpcProcessorConfig,
 };
 use ahash::AHashMap;
@@ -46,6 +48,7 @@ pub struct TokenProcessor {
     connection_pool: PgDbPool,
     config: TokenProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl TokenProcessor {
@@ -53,11 +56,13 @@ impl TokenProcessor {
         connection_pool: PgDbPool,
         config: TokenProcessorConfig,
         per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
     ) -> Self {
         Self {
             connection_pool,
             config,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -93,6 +98,7 @@ async fn insert_to_db(
     current_token_claims: &[CurrentTokenPendingClaim],
     nft_points: &[NftPoints],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -106,24 +112,28 @@ async fn insert_to_db(
         insert_tokens_query,
         tokens,
         get_config_table_chunk_size::<Token>("tokens", per_table_chunk_sizes),
+        db_write_config,
     );
     let to = execute_in_chunks(
         conn.clone(),
         insert_token_ownerships_query,
         token_ownerships,
         get_config_table_chunk_size::<TokenOwnership>("token_ownerships", per_table_chunk_sizes),
+        db_write_config,
     );
     let td = execute_in_chunks(
         conn.clone(),
         insert_token_datas_query,
         token_datas,
         get_config_table_chunk_size::<TokenData>("token_datas", per_table_chunk_sizes),
+        db_write_config,
     );
     let cd = execute_in_chunks(
         conn.clone(),
         insert_collection_datas_query,
         collection_datas,
         get_config_table_chunk_size::<CollectionData>("collection_datas", per_table_chunk_sizes),
+        db_write_config,
     );
     let cto = execute_in_chunks(
         conn.clone(),
@@ -133,6 +143,7 @@ async fn insert_to_db(
             "current_token_ownerships",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let ctd = execute_in_chunks(
         conn.clone(),
@@ -142,6 +153,7 @@ async fn insert_to_db(
             "current_token_datas",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let ccd = execute_in_chunks(
         conn.clone(),
@@ -151,6 +163,7 @@ async fn insert_to_db(
             "current_collection_datas",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
 
     let ta = execute_in_chunks(
@@ -158,6 +171,7 @@ async fn insert_to_db(
         insert_token_activities_query,
         token_activities,
         get_config_table_chunk_size::<TokenActivity>("token_activities", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let ctc = execute_in_chunks(
@@ -168,12 +182,14 @@ async fn insert_to_db(
             "current_token_pending_claims",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let np = execute_in_chunks(
         conn,
         insert_nft_points_query,
         nft_points,
         get_config_table_chunk_size::<NftPoints>("nft_points", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let (t_res, to_res, td_res, cd_res, cto_res, ctd_res, ccd_res, ta_res, ctc_res, np) =
@@ -436,7 +452,7 @@ impl ProcessorTrait for TokenProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut conn = self.get_conn().await;
         let query_retries = self.config.query_retries;
@@ -576,6 +592,7 @@ impl ProcessorTrait for TokenProcessor {
             &all_current_token_claims,
             &all_nft_points,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -604,4 +621,29 @@ impl ProcessorTrait for TokenProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<Token>("tokens", &["inserted_at"]),
+            TableFieldCount::new::<TokenOwnership>("token_ownerships", &["inserted_at"]),
+            TableFieldCount::new::<TokenData>("token_datas", &["inserted_at"]),
+            TableFieldCount::new::<CollectionData>("collection_datas", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTokenOwnership>("current_token_ownerships", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<CurrentTokenData>("current_token_datas", &["inserted_at"]),
+            TableFieldCount::new::<CurrentCollectionData>("current_collection_datas", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<TokenActivity>("token_activities", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTokenPendingClaim>("current_token_pending_claims", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<NftPoints>("nft_points", &["inserted_at"]),
+        ]
+    }
 }
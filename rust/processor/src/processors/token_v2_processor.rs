@@ -19,16 +19,19 @@ use crate::{
                 TokenOwnershipV2,
             },
             v2_token_utils::{
-                AptosCollection, Burn, BurnEvent, ConcurrentSupply, FixedSupply, MintEvent,
+                AptosCollection, Burn, BurnEvent, ConcurrentSupply, FixedSupply, Mint, MintEvent,
                 PropertyMapModel, TokenIdentifiers, TokenV2, TokenV2Burned, TokenV2Minted,
-                TransferEvent, UnlimitedSupply,
+                TokenV2Transferred, TransferEvent, UnlimitedSupply,
             },
         },
     },
     schema,
     utils::{
         counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool, PgPoolConnection},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            PgPoolConnection, TableFieldCount,
+        },
         util::{get_entry_function_from_user_request, parse_timestamp, standardize_address},
     },
     IndexerGrpcProcessorConfig,
@@ -59,6 +62,7 @@ pub struct TokenV2Processor {
     connection_pool: PgDbPool,
     config: TokenV2ProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl TokenV2Processor {
@@ -66,11 +70,13 @@ impl TokenV2Processor {
         connection_pool: PgDbPool,
         config: TokenV2ProcessorConfig,
         per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
     ) -> Self {
         Self {
             connection_pool,
             config,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -106,6 +112,7 @@ async fn insert_to_db(
     token_activities_v2: &[TokenActivityV2],
     current_token_v2_metadata: &[CurrentTokenV2Metadata],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -119,12 +126,14 @@ async fn insert_to_db(
         insert_collections_v2_query,
         collections_v2,
         get_config_table_chunk_size::<CollectionV2>("collections_v2", per_table_chunk_sizes),
+        db_write_config,
     );
     let td_v2 = execute_in_chunks(
         conn.clone(),
         insert_token_datas_v2_query,
         token_datas_v2,
         get_config_table_chunk_size::<TokenDataV2>("token_datas_v2", per_table_chunk_sizes),
+        db_write_config,
     );
     let to_v2 = execute_in_chunks(
         conn.clone(),
@@ -134,6 +143,7 @@ async fn insert_to_db(
             "token_ownerships_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cc_v2 = execute_in_chunks(
         conn.clone(),
@@ -143,6 +153,7 @@ async fn insert_to_db(
             "current_collections_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let ctd_v2 = execute_in_chunks(
         conn.clone(),
@@ -152,6 +163,7 @@ async fn insert_to_db(
             "current_token_datas_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cdtd_v2 = execute_in_chunks(
         conn.clone(),
@@ -161,6 +173,7 @@ async fn insert_to_db(
             "current_token_datas_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cto_v2 = execute_in_chunks(
         conn.clone(),
@@ -170,6 +183,7 @@ async fn insert_to_db(
             "current_token_ownerships_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let cdto_v2 = execute_in_chunks(
         conn.clone(),
@@ -179,6 +193,7 @@ async fn insert_to_db(
             "current_token_ownerships_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let ta_v2 = execute_in_chunks(
         conn.clone(),
@@ -188,6 +203,7 @@ async fn insert_to_db(
             "token_activities_v2",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let ct_v2 = execute_in_chunks(
         conn,
@@ -197,6 +213,7 @@ async fn insert_to_db(
             "current_token_v2_metadata",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
 
     let (
@@ -451,6 +468,7 @@ fn insert_token_activities_v2_query(
             .set((
                 is_fungible_v2.eq(excluded(is_fungible_v2)),
                 inserted_at.eq(excluded(inserted_at)),
+                is_transient.eq(excluded(is_transient)),
             )),
         None,
     )
@@ -493,7 +511,7 @@ impl ProcessorTrait for TokenV2Processor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut conn = self.get_conn().await;
 
@@ -545,6 +563,7 @@ impl ProcessorTrait for TokenV2Processor {
             &token_activities_v2,
             &current_token_v2_metadata,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
 
@@ -573,6 +592,30 @@ impl ProcessorTrait for TokenV2Processor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<CollectionV2>("collections_v2", &["inserted_at"]),
+            TableFieldCount::new::<TokenDataV2>("token_datas_v2", &[
+                "inserted_at",
+                "is_deleted_v2",
+            ]),
+            TableFieldCount::new::<TokenOwnershipV2>("token_ownerships_v2", &["inserted_at"]),
+            TableFieldCount::new::<CurrentCollectionV2>("current_collections_v2", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTokenDataV2>("current_token_datas_v2", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTokenOwnershipV2>("current_token_ownerships_v2", &[
+                "inserted_at",
+            ]),
+            TableFieldCount::new::<TokenActivityV2>("token_activities_v2", &["inserted_at"]),
+            TableFieldCount::new::<CurrentTokenV2Metadata>("current_token_v2_metadata", &[
+                "inserted_at",
+            ]),
+        ]
+    }
 }
 
 async fn parse_v2_token(
@@ -652,6 +695,9 @@ async fn parse_v2_token(
             // Get mint events for token v2 by object
             let mut tokens_minted: TokenV2Minted = AHashSet::new();
 
+            // Get transfer events for token v2 by object
+            let mut tokens_transferred: TokenV2Transferred = AHashMap::new();
+
             // Need to do a first pass to get all the objects
             for wsc in transaction_info.changes.iter() {
                 if let Change::WriteResource(wr) = wsc.change.as_ref().unwrap() {
@@ -717,9 +763,9 @@ async fn parse_v2_token(
                 }
             }
 
-            // Pass through events to get the burn events and token activities v2
-            // This needs to be here because we need the metadata above for token activities
-            // and burn / transfer events need to come before the next section
+            // Pass through events to get the burn, mint and transfer events
+            // These need to come before the token activities v2, which look up later events,
+            // and before the next section
             for (index, event) in user_txn.events.iter().enumerate() {
                 if let Some(burn_event) = Burn::from_event(event, txn_version).unwrap() {
                     tokens_burned.insert(burn_event.get_token_address(), burn_event);
@@ -735,9 +781,16 @@ async fn parse_v2_token(
                 if let Some(mint_event) = MintEvent::from_event(event, txn_version).unwrap() {
                     tokens_minted.insert(mint_event.get_token_address());
                 }
+                if let Some(mint_event) = Mint::from_event(event, txn_version).unwrap() {
+                    tokens_minted.insert(mint_event.get_token_address());
+                }
                 if let Some(transfer_events) =
                     TransferEvent::from_event(event, txn_version).unwrap()
                 {
+                    tokens_transferred
+                        .entry(transfer_events.get_object_address())
+                        .or_default()
+                        .push((index as i64, transfer_events.clone()));
                     if let Some(aggregated_data) =
                         token_v2_metadata_helper.get_mut(&transfer_events.get_object_address())
                     {
@@ -753,6 +806,11 @@ async fn parse_v2_token(
                             .push((index as i64, transfer_events));
                     }
                 }
+            }
+
+            // Pass through events to get the token activities v2
+            // This needs to be here because we need the metadata above for token activities
+            for (index, event) in user_txn.events.iter().enumerate() {
                 // handling all the token v1 events
                 if let Some(event) = TokenActivityV2::get_v1_from_parsed_event(
                     event,
@@ -773,6 +831,8 @@ async fn parse_v2_token(
                     index as i64,
                     &entry_function_id_str,
                     &token_v2_metadata_helper,
+                    &tokens_minted,
+                    &tokens_transferred,
                 )
                 .await
                 .unwrap()
@@ -8,7 +8,9 @@ use crate::{
         write_set_size_info::WriteSetSize,
     },
     schema,
-    utils::database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+    utils::database::{
+        execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool, TableFieldCount,
+    },
 };
 use ahash::AHashMap;
 use anyhow::bail;
@@ -21,13 +23,19 @@ use tracing::{error, warn};
 pub struct TransactionMetadataProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl TransactionMetadataProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -52,6 +60,7 @@ async fn insert_to_db(
     event_sizes: &[EventSize],
     write_set_sizes: &[WriteSetSize],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -68,6 +77,7 @@ async fn insert_to_db(
             "transaction_size_info",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     )
     .await?;
     execute_in_chunks(
@@ -75,6 +85,7 @@ async fn insert_to_db(
         insert_event_sizes_query,
         event_sizes,
         get_config_table_chunk_size::<EventSize>("event_size_info", per_table_chunk_sizes),
+        db_write_config,
     )
     .await?;
     execute_in_chunks(
@@ -82,6 +93,7 @@ async fn insert_to_db(
         insert_write_set_sizes_query,
         write_set_sizes,
         get_config_table_chunk_size::<WriteSetSize>("write_set_size_info", per_table_chunk_sizes),
+        db_write_config,
     )
     .await?;
 
@@ -194,6 +206,7 @@ impl ProcessorTrait for TransactionMetadataProcessor {
             &event_sizes,
             &write_set_sizes,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
         let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
@@ -203,7 +216,7 @@ impl ProcessorTrait for TransactionMetadataProcessor {
                 end_version,
                 processing_duration_in_secs,
                 db_insertion_duration_in_secs,
-                last_transaction_timestamp: transactions.last().unwrap().timestamp.clone(),
+                last_transaction_timestamp: transactions.last().and_then(|t| t.timestamp.clone()),
             }),
             Err(e) => {
                 error!(
@@ -221,4 +234,16 @@ impl ProcessorTrait for TransactionMetadataProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<TransactionSize>("transaction_size_info", &["inserted_at"]),
+            TableFieldCount::new::<EventSize>("event_size_info", &["inserted_at"]),
+            TableFieldCount::new::<WriteSetSize>("write_set_size_info", &["inserted_at"]),
+        ]
+    }
 }
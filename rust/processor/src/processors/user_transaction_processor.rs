@@ -9,7 +9,10 @@ use crate::{
     schema,
     utils::{
         counters::PROCESSOR_UNKNOWN_TYPE_COUNT,
-        database::{execute_in_chunks, get_config_table_chunk_size, PgDbPool},
+        database::{
+            execute_in_chunks, get_config_table_chunk_size, DbWriteConfig, PgDbPool,
+            TableFieldCount,
+        },
     },
 };
 use ahash::AHashMap;
@@ -27,13 +30,19 @@ use tracing::error;
 pub struct UserTransactionProcessor {
     connection_pool: PgDbPool,
     per_table_chunk_sizes: AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 }
 
 impl UserTransactionProcessor {
-    pub fn new(connection_pool: PgDbPool, per_table_chunk_sizes: AHashMap<String, usize>) -> Self {
+    pub fn new(
+        connection_pool: PgDbPool,
+        per_table_chunk_sizes: AHashMap<String, usize>,
+        db_write_config: DbWriteConfig,
+    ) -> Self {
         Self {
             connection_pool,
             per_table_chunk_sizes,
+            db_write_config,
         }
     }
 }
@@ -57,6 +66,7 @@ async fn insert_to_db(
     user_transactions: &[UserTransactionModel],
     signatures: &[Signature],
     per_table_chunk_sizes: &AHashMap<String, usize>,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error> {
     tracing::trace!(
         name = name,
@@ -73,12 +83,14 @@ async fn insert_to_db(
             "user_transactions",
             per_table_chunk_sizes,
         ),
+        db_write_config,
     );
     let is = execute_in_chunks(
         conn,
         insert_signatures_query,
         signatures,
         get_config_table_chunk_size::<Signature>("signatures", per_table_chunk_sizes),
+        db_write_config,
     );
 
     let (ut_res, is_res) = futures::join!(ut, is);
@@ -143,7 +155,7 @@ impl ProcessorTrait for UserTransactionProcessor {
         _: Option<u64>,
     ) -> anyhow::Result<ProcessingResult> {
         let processing_start = std::time::Instant::now();
-        let last_transaction_timestamp = transactions.last().unwrap().timestamp.clone();
+        let last_transaction_timestamp = transactions.last().and_then(|t| t.timestamp.clone());
 
         let mut signatures = vec![];
         let mut user_transactions = vec![];
@@ -187,6 +199,7 @@ impl ProcessorTrait for UserTransactionProcessor {
             &user_transactions,
             &signatures,
             &self.per_table_chunk_sizes,
+            self.db_write_config,
         )
         .await;
         let db_insertion_duration_in_secs = db_insertion_start.elapsed().as_secs_f64();
@@ -214,4 +227,15 @@ impl ProcessorTrait for UserTransactionProcessor {
     fn connection_pool(&self) -> &PgDbPool {
         &self.connection_pool
     }
+
+    fn db_write_config(&self) -> DbWriteConfig {
+        self.db_write_config
+    }
+
+    fn table_field_counts(&self) -> Vec<TableFieldCount> {
+        vec![
+            TableFieldCount::new::<UserTransactionModel>("user_transactions", &["inserted_at"]),
+            TableFieldCount::new::<Signature>("signatures", &["inserted_at"]),
+        ]
+    }
 }
@@ -723,6 +723,7 @@ diesel::table! {
         module_address -> Varchar,
         module_name -> Varchar,
         event_name -> Varchar,
+        epoch -> Nullable<Int8>,
     }
 }
 
@@ -1023,6 +1024,7 @@ diesel::table! {
         is_fungible_v2 -> Nullable<Bool>,
         transaction_timestamp -> Timestamp,
         inserted_at -> Timestamp,
+        is_transient -> Nullable<Bool>,
     }
 }
 
@@ -19,6 +19,8 @@ pub struct TransactionFilter {
     skip_sender_addresses: Option<ahash::HashSet<String>>,
     // Skip all transactions that aren't user transactions
     focus_user_transactions: bool,
+    // Skip transactions with a timestamp before this, in unix seconds
+    min_transaction_timestamp: Option<i64>,
 }
 
 impl TransactionFilter {
@@ -26,17 +28,29 @@ impl TransactionFilter {
         focus_contract_addresses: Option<ahash::HashSet<String>>,
         skip_sender_addresses: Option<ahash::HashSet<String>>,
         focus_user_transactions: bool,
+        min_transaction_timestamp: Option<i64>,
     ) -> Self {
         // TODO: normalize addresses
         Self {
             focus_contract_addresses,
             skip_sender_addresses,
             focus_user_transactions,
+            min_transaction_timestamp,
         }
     }
 
     /// Returns true if the transaction should be included
     pub fn include(&self, transaction: &Transaction) -> bool {
+        // Skip transactions older than the cutoff. Transactions without a timestamp are kept
+        if let (Some(min_timestamp), Some(timestamp)) = (
+            self.min_transaction_timestamp,
+            transaction.timestamp.as_ref(),
+        ) {
+            if timestamp.seconds < min_timestamp {
+                return false;
+            }
+        }
+
         // If we're only focusing on user transactions, skip if it's not a user transaction
 
         let is_user_txn = transaction.r#type == TransactionType::User as i32;
@@ -78,3 +92,39 @@ impl TransactionFilter {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::util::timestamp::Timestamp;
+
+    fn transaction_at(version: u64, seconds: i64) -> Transaction {
+        Transaction {
+            version,
+            timestamp: Some(Timestamp { seconds, nanos: 0 }),
+            r#type: TransactionType::BlockMetadata as i32,
+            ..Transaction::default()
+        }
+    }
+
+    #[test]
+    fn test_min_transaction_timestamp() {
+        let filter = TransactionFilter::new(None, None, false, Some(1_700_000_000));
+        let mut transactions = vec![
+            transaction_at(1, 1_600_000_000),
+            transaction_at(2, 1_699_999_999),
+            transaction_at(3, 1_700_000_000),
+            transaction_at(4, 1_700_000_001),
+            Transaction {
+                version: 5,
+                ..Transaction::default()
+            },
+        ];
+        transactions.retain(|txn| filter.include(txn));
+        let versions: Vec<u64> = transactions.iter().map(|txn| txn.version).collect();
+        assert_eq!(versions, vec![3, 4, 5]);
+
+        let filter = TransactionFilter::default();
+        assert!(filter.include(&transaction_at(1, 0)));
+    }
+}
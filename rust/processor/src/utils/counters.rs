@@ -59,6 +59,17 @@ pub static PROCESSOR_DATA_PROCESSED_LATENCY_IN_SECS: Lazy<GaugeVec> = Lazy::new(
     .unwrap()
 });
 
+/// Seconds between now and the timestamp of the last transaction stored in processor_status.
+/// Unlike the processed latency above, this is only set once the version is checkpointed.
+pub static PROCESSOR_LAG_IN_SECS: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "indexer_processor_lag_in_secs",
+        "Seconds between now and the last successfully processed transaction",
+        &["processor_name"]
+    )
+    .unwrap()
+});
+
 /// Number of times a given processor has been invoked
 pub static PROCESSOR_INVOCATIONS_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -245,6 +256,16 @@ pub static GRPC_LATENCY_BY_PROCESSOR_IN_SECS: Lazy<GaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Token v2 activities whose token object isn't in the transaction, e.g. burned tokens.
+pub static TOKEN_V2_MISSING_METADATA_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "indexer_processor_token_v2_missing_metadata_count",
+        "Token v2 activities without token object metadata in the transaction",
+        &["event_type"]
+    )
+    .unwrap()
+});
+
 /// Processor unknown type count.
 pub static PROCESSOR_UNKNOWN_TYPE_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
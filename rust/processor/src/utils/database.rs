@@ -9,6 +9,7 @@ use ahash::AHashMap;
 use diesel::{
     backend::Backend,
     query_builder::{AstPass, Query, QueryFragment},
+    result::{DatabaseErrorInformation, DatabaseErrorKind},
     ConnectionResult, QueryResult,
 };
 use diesel_async::{
@@ -21,7 +22,8 @@ use diesel_async::{
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use futures_util::{future::BoxFuture, FutureExt};
-use std::sync::Arc;
+use serde::Serialize;
+use std::{future::Future, sync::Arc, time::Duration};
 
 pub type MyDbConnection = AsyncPgConnection;
 pub type PgPool = Pool<MyDbConnection>;
@@ -32,6 +34,30 @@ pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
 
 pub const DEFAULT_MAX_POOL_SIZE: u32 = 150;
 
+pub const DEFAULT_DB_WRITE_RETRIES: u32 = 3;
+const DB_WRITE_BASE_BACKOFF_MS: u64 = 100;
+// How much of the SQL of a failing query is added to its error
+const MAX_QUERY_CONTEXT_BYTES: usize = 2048;
+
+/// How processors write to the database, passed from the config down to every write
+#[derive(Clone, Copy, Debug)]
+pub struct DbWriteConfig {
+    /// Parse transactions as usual but skip all writes, only logging what would have been written
+    pub dry_run: bool,
+    /// Number of times a write is retried on transient errors, i.e. serialization failures and
+    /// deadlocks from concurrent upserts
+    pub max_retries: u32,
+}
+
+impl Default for DbWriteConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: false,
+            max_retries: DEFAULT_DB_WRITE_RETRIES,
+        }
+    }
+}
+
 #[derive(QueryId)]
 /// Using this will append a where clause at the end of the string upsert function, e.g.
 /// INSERT INTO ... ON CONFLICT DO UPDATE SET ... WHERE "transaction_version" = excluded."transaction_version"
@@ -123,24 +149,58 @@ pub async fn new_db_pool(
     Ok(Arc::new(pool))
 }
 
+/// Snapshot of the connections in the pool, e.g. for readiness checks.
+/// bb8 doesn't expose how many tasks are waiting for a connection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct PoolState {
+    pub total: u32,
+    pub idle: u32,
+    pub in_use: u32,
+}
+
+impl PoolState {
+    pub fn from_pool<M: bb8::ManageConnection>(pool: &bb8::Pool<M>) -> Self {
+        let state = pool.state();
+        Self {
+            total: state.connections,
+            idle: state.idle_connections,
+            in_use: state.connections.saturating_sub(state.idle_connections),
+        }
+    }
+
+    /// Whether a new query would have to wait for a connection, i.e. none is idle and
+    /// the pool can't open more than `max_pool_size`.
+    pub fn is_saturated(&self, max_pool_size: u32) -> bool {
+        self.idle == 0 && self.total >= max_pool_size
+    }
+}
+
 pub async fn execute_in_chunks<U, T>(
     conn: PgDbPool,
     build_query: fn(Vec<T>) -> (U, Option<&'static str>),
     items_to_insert: &[T],
     chunk_size: usize,
+    db_write_config: DbWriteConfig,
 ) -> Result<(), diesel::result::Error>
 where
     U: QueryFragment<diesel::pg::Pg> + diesel::query_builder::QueryId + Send + 'static,
     T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone + Send + 'static,
 {
+    if db_write_config.dry_run {
+        tracing::info!(
+            model = std::any::type_name::<T>(),
+            num_rows = items_to_insert.len(),
+            "[Parser] Dry run, skipping insert"
+        );
+        return Ok(());
+    }
     let tasks = items_to_insert
         .chunks(chunk_size)
         .map(|chunk| {
             let conn = conn.clone();
             let items = chunk.to_vec();
             tokio::spawn(async move {
-                let (query, additional_where_clause) = build_query(items.clone());
-                execute_or_retry_cleaned(conn, build_query, items, query, additional_where_clause)
+                execute_or_retry_cleaned(conn, build_query, items, db_write_config.max_retries)
                     .await
             })
         })
@@ -187,19 +247,98 @@ where
     if let Err(ref e) = res {
         tracing::warn!("Error running query: {:?}\n{:?}", e, debug_string);
     }
-    res
+    res.map_err(|e| with_query_context(e, debug_string))
+}
+
+/// Database error message with the SQL of the failing query appended, so it isn't lost
+/// once the error is bubbled up. Everything else is from the original error.
+#[derive(Debug)]
+struct DatabaseErrorWithQuery {
+    message: String,
+    info: Box<dyn DatabaseErrorInformation + Send + Sync>,
+}
+
+impl DatabaseErrorInformation for DatabaseErrorWithQuery {
+    fn message(&self) -> &str {
+        &self.message
+    }
+
+    fn details(&self) -> Option<&str> {
+        self.info.details()
+    }
+
+    fn hint(&self) -> Option<&str> {
+        self.info.hint()
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        self.info.table_name()
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        self.info.column_name()
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        self.info.constraint_name()
+    }
+
+    fn statement_position(&self) -> Option<i32> {
+        self.info.statement_position()
+    }
+}
+
+/// Adds the start of the debug SQL of the query to database errors, which is enough to tell
+/// the table and columns apart. Other errors are returned as is.
+fn with_query_context(error: diesel::result::Error, debug_string: String) -> diesel::result::Error {
+    match error {
+        diesel::result::Error::DatabaseError(kind, info) => {
+            let message = if debug_string.len() > MAX_QUERY_CONTEXT_BYTES {
+                // Chunks can be megabytes of SQL, so only keep the start
+                let end = (0..=MAX_QUERY_CONTEXT_BYTES)
+                    .rev()
+                    .find(|i| debug_string.is_char_boundary(*i))
+                    .unwrap_or(0);
+                format!(
+                    "{}\nQuery: {}... ({} bytes truncated)",
+                    info.message(),
+                    &debug_string[..end],
+                    debug_string.len() - end
+                )
+            } else {
+                format!("{}\nQuery: {}", info.message(), debug_string)
+            };
+            diesel::result::Error::DatabaseError(
+                kind,
+                Box::new(DatabaseErrorWithQuery { message, info }),
+            )
+        },
+        e => e,
+    }
 }
 
 /// Returns the entry for the config hashmap, or the default field count for the insert
 /// Given diesel has a limit of how many parameters can be inserted in a single operation (u16::MAX),
 /// we default to chunk an array of items based on how many columns are in the table.
+/// Configured sizes are clamped so that a chunk never goes over that limit.
 pub fn get_config_table_chunk_size<T: field_count::FieldCount>(
     table_name: &str,
     per_table_chunk_sizes: &AHashMap<String, usize>,
 ) -> usize {
+    let max_chunk_size = (u16::MAX as usize / T::field_count()).max(1);
     per_table_chunk_sizes
         .get(table_name)
-        .copied()
+        .map(|chunk_size| {
+            if *chunk_size > max_chunk_size {
+                tracing::warn!(
+                    table_name = table_name,
+                    chunk_size = chunk_size,
+                    max_chunk_size = max_chunk_size,
+                    "Configured chunk size is over the postgres parameter limit, clamping it"
+                );
+            }
+            (*chunk_size).clamp(1, max_chunk_size)
+        })
         .unwrap_or_else(|| MAX_DIESEL_PARAM_SIZE / T::field_count())
 }
 
@@ -227,43 +366,166 @@ where
     if let Err(ref e) = res {
         tracing::warn!("Error running query: {:?}\n{:?}", e, debug_string);
     }
-    res
+    res.map_err(|e| with_query_context(e, debug_string))
 }
 
 async fn execute_or_retry_cleaned<U, T>(
     conn: PgDbPool,
     build_query: fn(Vec<T>) -> (U, Option<&'static str>),
     items: Vec<T>,
-    query: U,
-    additional_where_clause: Option<&'static str>,
+    max_retries: u32,
 ) -> Result<(), diesel::result::Error>
 where
     U: QueryFragment<diesel::pg::Pg> + diesel::query_builder::QueryId + Send,
     T: serde::Serialize + for<'de> serde::Deserialize<'de> + Clone,
 {
-    match execute_with_better_error(conn.clone(), query, additional_where_clause).await {
-        Ok(_) => {},
-        Err(_) => {
-            let cleaned_items = clean_data_for_db(items, true);
-            let (cleaned_query, additional_where_clause) = build_query(cleaned_items);
-            match execute_with_better_error(conn.clone(), cleaned_query, additional_where_clause)
-                .await
-            {
-                Ok(_) => {},
-                Err(e) => {
-                    return Err(e);
-                },
-            }
-        },
+    let query_items = items.clone();
+    let query_conn = conn.clone();
+    let res = retry_on_transient_error(max_retries, move || {
+        let (query, additional_where_clause) = build_query(query_items.clone());
+        execute_with_better_error(query_conn.clone(), query, additional_where_clause)
+    })
+    .await;
+    if res.is_err() {
+        let cleaned_items = clean_data_for_db(items, true);
+        retry_on_transient_error(max_retries, move || {
+            let (cleaned_query, additional_where_clause) = build_query(cleaned_items.clone());
+            execute_with_better_error(conn.clone(), cleaned_query, additional_where_clause)
+        })
+        .await?;
     }
     Ok(())
 }
 
+/// Serialization failures (SQLSTATE 40001) and deadlocks (40P01) are expected under
+/// concurrent upserts and usually succeed when retried.
+fn is_transient_error(error: &diesel::result::Error) -> bool {
+    match error {
+        diesel::result::Error::DatabaseError(DatabaseErrorKind::SerializationFailure, _) => true,
+        // Diesel doesn't have a kind for deadlocks (SQLSTATE 40P01) and doesn't expose the
+        // SQLSTATE, so this matches postgres' message. That assumes lc_messages is English,
+        // otherwise deadlocks aren't retried.
+        diesel::result::Error::DatabaseError(DatabaseErrorKind::Unknown, info) => {
+            info.message().starts_with("deadlock detected")
+        },
+        _ => false,
+    }
+}
+
+/// Runs the query built by `execute`, retrying up to `max_retries` times with exponential
+/// backoff while it fails with a transient error.
+async fn retry_on_transient_error<F, Fut, R>(max_retries: u32, mut execute: F) -> QueryResult<R>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = QueryResult<R>>,
+{
+    let mut retries = 0;
+    loop {
+        match execute().await {
+            Err(e) if retries < max_retries && is_transient_error(&e) => {
+                retries += 1;
+                let backoff = Duration::from_millis(DB_WRITE_BASE_BACKOFF_MS << retries.min(6));
+                tracing::warn!(
+                    retries = retries,
+                    error = ?e,
+                    "Transient error running query, will retry in {:?}",
+                    backoff
+                );
+                tokio::time::sleep(backoff).await;
+            },
+            res => return res,
+        }
+    }
+}
+
 pub fn run_pending_migrations<DB: Backend>(conn: &mut impl MigrationHarness<DB>) {
     conn.run_pending_migrations(MIGRATIONS)
         .expect("[Parser] Migrations failed!");
 }
 
+/// A dry run must not change the schema, so it fails instead of running pending migrations
+pub fn check_no_pending_migrations<DB: Backend>(conn: &mut impl MigrationHarness<DB>) {
+    let has_pending_migration = conn
+        .has_pending_migration(MIGRATIONS)
+        .expect("[Parser] Failed to check for pending migrations!");
+    assert!(
+        !has_pending_migration,
+        "[Parser] Dry run needs an up to date database but there are pending migrations"
+    );
+}
+
+/// A table a processor inserts into, with the number of fields of the model inserted into it
+#[derive(Clone, Debug)]
+pub struct TableFieldCount {
+    pub table_name: &'static str,
+    pub field_count: usize,
+    /// Columns the model leaves out on purpose, e.g. `inserted_at` which defaults to now()
+    pub omitted_columns: &'static [&'static str],
+}
+
+impl TableFieldCount {
+    pub fn new<T: field_count::FieldCount>(
+        table_name: &'static str,
+        omitted_columns: &'static [&'static str],
+    ) -> Self {
+        Self {
+            table_name,
+            field_count: T::field_count(),
+            omitted_columns,
+        }
+    }
+}
+
+#[derive(Debug, QueryableByName)]
+struct ColumnName {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    column_name: String,
+}
+
+/// Checks that a model has a field for every column of its table but the omitted ones.
+/// Chunk sizes are derived from the model's field count, so a model that drifted from its
+/// table would also get them wrong.
+pub async fn check_table_field_count(
+    conn: &mut PgPoolConnection<'_>,
+    table: &TableFieldCount,
+) -> anyhow::Result<()> {
+    let columns: Vec<ColumnName> = diesel::sql_query(
+        "SELECT column_name::TEXT AS column_name FROM information_schema.columns \
+        WHERE table_schema = current_schema() AND table_name = $1",
+    )
+    .bind::<diesel::sql_types::Text, _>(table.table_name)
+    .get_results(conn)
+    .await?;
+    let columns: Vec<String> = columns.into_iter().map(|c| c.column_name).collect();
+    validate_table_field_count(table, &columns)
+}
+
+fn validate_table_field_count(table: &TableFieldCount, columns: &[String]) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        !columns.is_empty(),
+        "Table {} doesn't exist",
+        table.table_name
+    );
+    for omitted_column in table.omitted_columns {
+        anyhow::ensure!(
+            columns.iter().any(|column| column == omitted_column),
+            "Column {} omitted by the model for table {} doesn't exist",
+            omitted_column,
+            table.table_name
+        );
+    }
+    let expected_field_count = columns.len() - table.omitted_columns.len();
+    anyhow::ensure!(
+        table.field_count == expected_field_count,
+        "Model for table {} has {} fields but the table has {} columns besides the omitted {:?}",
+        table.table_name,
+        table.field_count,
+        expected_field_count,
+        table.omitted_columns
+    );
+    Ok(())
+}
+
 /// Section below is required to modify the query.
 impl<T: Query> Query for UpsertFilterLatestTransactionQuery<T> {
     type SqlType = T::SqlType;
@@ -283,3 +545,368 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema;
+    use diesel::{
+        pg::{upsert::excluded, Pg},
+        result::Error,
+        ExpressionMethods,
+    };
+
+    #[test]
+    fn test_database_error_includes_query() {
+        use schema::current_coin_balances::dsl::*;
+        let query = UpsertFilterLatestTransactionQuery {
+            query: diesel::insert_into(schema::current_coin_balances::table)
+                .values((
+                    owner_address.eq("0x1"),
+                    coin_type_hash.eq("hash"),
+                    coin_type.eq("0x1::aptos_coin::AptosCoin"),
+                ))
+                .on_conflict((owner_address, coin_type_hash))
+                .do_update()
+                .set(coin_type.eq(excluded(coin_type))),
+            where_clause: Some(
+                " WHERE current_coin_balances.last_transaction_version <= excluded.last_transaction_version ",
+            ),
+        };
+        let debug_string = diesel::debug_query::<Pg, _>(&query).to_string();
+        let error = with_query_context(
+            Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new("duplicate key value violates unique constraint".to_string()),
+            ),
+            debug_string,
+        );
+
+        let Error::DatabaseError(kind, info) = error else {
+            panic!("Expected a database error");
+        };
+        assert!(matches!(kind, DatabaseErrorKind::UniqueViolation));
+        assert!(info
+            .message()
+            .starts_with("duplicate key value violates unique constraint"));
+        assert!(info
+            .message()
+            .contains("INSERT INTO \"current_coin_balances\""));
+        assert!(info
+            .message()
+            .contains("WHERE current_coin_balances.last_transaction_version"));
+    }
+
+    fn insert_ledger_infos_query(
+        items: Vec<String>,
+    ) -> (
+        impl QueryFragment<Pg> + diesel::query_builder::QueryId + Send,
+        Option<&'static str>,
+    ) {
+        use schema::ledger_infos::dsl::*;
+        (
+            diesel::insert_into(schema::ledger_infos::table)
+                .values(chain_id.eq(items.len() as i64))
+                .on_conflict_do_nothing(),
+            None,
+        )
+    }
+
+    /// Nothing listens on this port, so any attempt to write fails
+    fn unreachable_pool() -> PgDbPool {
+        let manager = AsyncDieselConnectionManager::<MyDbConnection>::new(
+            "postgres://postgres@127.0.0.1:1/postgres",
+        );
+        Arc::new(
+            Pool::builder()
+                .connection_timeout(std::time::Duration::from_millis(100))
+                .build_unchecked(manager),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_skips_inserts() {
+        let pool = unreachable_pool();
+        let items = vec!["a".to_string(), "b".to_string()];
+
+        let dry_run = DbWriteConfig {
+            dry_run: true,
+            ..DbWriteConfig::default()
+        };
+        assert!(
+            execute_in_chunks(pool.clone(), insert_ledger_infos_query, &items, 1, dry_run)
+                .await
+                .is_ok()
+        );
+        assert_eq!(pool.state().connections, 0);
+        assert!(execute_in_chunks(
+            pool,
+            insert_ledger_infos_query,
+            &items,
+            1,
+            DbWriteConfig::default()
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execute_in_chunks_with_no_items() {
+        let pool = unreachable_pool();
+        let items: Vec<String> = vec![];
+        assert!(execute_in_chunks(
+            pool.clone(),
+            insert_ledger_infos_query,
+            &items,
+            1,
+            DbWriteConfig::default()
+        )
+        .await
+        .is_ok());
+        assert_eq!(pool.state().connections, 0);
+    }
+
+    #[test]
+    fn test_config_table_chunk_size() {
+        use crate::models::events_models::events::EventModel;
+        use field_count::FieldCount;
+
+        let max_chunk_size = u16::MAX as usize / EventModel::field_count();
+        let per_table_chunk_sizes: AHashMap<String, usize> = [
+            ("events".to_string(), 2),
+            ("too_large".to_string(), usize::MAX),
+            ("zero".to_string(), 0),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            get_config_table_chunk_size::<EventModel>("events", &per_table_chunk_sizes),
+            2
+        );
+        assert_eq!(
+            get_config_table_chunk_size::<EventModel>("too_large", &per_table_chunk_sizes),
+            max_chunk_size
+        );
+        assert_eq!(
+            get_config_table_chunk_size::<EventModel>("zero", &per_table_chunk_sizes),
+            1
+        );
+        assert_eq!(
+            get_config_table_chunk_size::<EventModel>("missing", &per_table_chunk_sizes),
+            MAX_DIESEL_PARAM_SIZE / EventModel::field_count()
+        );
+    }
+
+    fn database_error(kind: DatabaseErrorKind, message: &str) -> Error {
+        Error::DatabaseError(kind, Box::new(message.to_string()))
+    }
+
+    #[test]
+    fn test_is_transient_error() {
+        assert!(is_transient_error(&database_error(
+            DatabaseErrorKind::SerializationFailure,
+            "could not serialize access due to concurrent update",
+        )));
+        assert!(is_transient_error(&database_error(
+            DatabaseErrorKind::Unknown,
+            "deadlock detected",
+        )));
+        assert!(!is_transient_error(&database_error(
+            DatabaseErrorKind::UniqueViolation,
+            "duplicate key value violates unique constraint",
+        )));
+        assert!(!is_transient_error(&database_error(
+            DatabaseErrorKind::Unknown,
+            "invalid byte sequence for encoding \"UTF8\": 0x00",
+        )));
+        assert!(!is_transient_error(&Error::NotFound));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_on_transient_error() {
+        // Retried until it succeeds
+        let mut attempts = 0;
+        let res = retry_on_transient_error(3, || {
+            attempts += 1;
+            let res = if attempts < 3 {
+                Err(database_error(
+                    DatabaseErrorKind::SerializationFailure,
+                    "could not serialize access",
+                ))
+            } else {
+                Ok(attempts)
+            };
+            async move { res }
+        })
+        .await;
+        assert_eq!(res.unwrap(), 3);
+
+        // Gives up after max retries
+        let mut attempts = 0;
+        let res: QueryResult<()> = retry_on_transient_error(2, || {
+            attempts += 1;
+            async {
+                Err(database_error(
+                    DatabaseErrorKind::Unknown,
+                    "deadlock detected",
+                ))
+            }
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 3);
+
+        // Not retried on other errors
+        let mut attempts = 0;
+        let res: QueryResult<()> = retry_on_transient_error(2, || {
+            attempts += 1;
+            async {
+                Err(database_error(
+                    DatabaseErrorKind::UniqueViolation,
+                    "duplicate key value violates unique constraint",
+                ))
+            }
+        })
+        .await;
+        assert!(res.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_on_deadlock() {
+        let mut attempts = 0;
+        let res = retry_on_transient_error(3, || {
+            attempts += 1;
+            let res = if attempts < 2 {
+                Err(database_error(
+                    DatabaseErrorKind::Unknown,
+                    "deadlock detected",
+                ))
+            } else {
+                Ok(attempts)
+            };
+            async move { res }
+        })
+        .await;
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    /// Hands out connections without a database so tests can check them out
+    struct NoopConnectionManager;
+
+    #[async_trait::async_trait]
+    impl bb8::ManageConnection for NoopConnectionManager {
+        type Connection = ();
+        type Error = std::convert::Infallible;
+
+        async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+            Ok(())
+        }
+
+        async fn is_valid(&self, _conn: &mut Self::Connection) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_state() {
+        let pool = bb8::Pool::builder()
+            .max_size(2)
+            .build(NoopConnectionManager)
+            .await
+            .unwrap();
+        let state = |total, idle, in_use| PoolState {
+            total,
+            idle,
+            in_use,
+        };
+        assert_eq!(PoolState::from_pool(&pool), state(0, 0, 0));
+        assert!(!PoolState::from_pool(&pool).is_saturated(2));
+
+        let first = pool.get().await.unwrap();
+        assert_eq!(PoolState::from_pool(&pool), state(1, 0, 1));
+        assert!(!PoolState::from_pool(&pool).is_saturated(2));
+
+        let second = pool.get().await.unwrap();
+        assert_eq!(PoolState::from_pool(&pool), state(2, 0, 2));
+        assert!(PoolState::from_pool(&pool).is_saturated(2));
+        assert!(!PoolState::from_pool(&pool).is_saturated(3));
+
+        drop(first);
+        assert_eq!(PoolState::from_pool(&pool), state(2, 1, 1));
+        assert!(!PoolState::from_pool(&pool).is_saturated(2));
+        drop(second);
+        assert_eq!(PoolState::from_pool(&pool), state(2, 2, 0));
+    }
+
+    #[test]
+    fn test_validate_table_field_count() {
+        let table = TableFieldCount {
+            table_name: "coin_supply",
+            field_count: 3,
+            omitted_columns: &["inserted_at"],
+        };
+        let columns = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert!(validate_table_field_count(
+            &table,
+            &columns(&["transaction_version", "coin_type", "supply", "inserted_at"])
+        )
+        .is_ok());
+        // A nullable column was added without updating the model
+        assert!(validate_table_field_count(
+            &table,
+            &columns(&[
+                "transaction_version",
+                "coin_type",
+                "supply",
+                "inserted_at",
+                "total_supply",
+            ])
+        )
+        .is_err());
+        // A field was added to the model without a migration
+        assert!(validate_table_field_count(
+            &table,
+            &columns(&["transaction_version", "coin_type", "inserted_at"])
+        )
+        .is_err());
+        // The omitted column was dropped
+        assert!(validate_table_field_count(
+            &table,
+            &columns(&["transaction_version", "coin_type", "supply", "epoch"])
+        )
+        .is_err());
+        assert!(validate_table_field_count(&table, &[]).is_err());
+    }
+
+    #[test]
+    fn test_database_error_query_is_truncated() {
+        let debug_string = format!("INSERT INTO \"events\" VALUES {}", "('é'), ".repeat(10_000));
+        let error = with_query_context(
+            Error::DatabaseError(
+                DatabaseErrorKind::UniqueViolation,
+                Box::new("duplicate key value violates unique constraint".to_string()),
+            ),
+            debug_string.clone(),
+        );
+
+        let Error::DatabaseError(_, info) = error else {
+            panic!("Expected a database error");
+        };
+        assert!(info.message().len() < MAX_QUERY_CONTEXT_BYTES + 200);
+        assert!(info.message().contains("INSERT INTO \"events\""));
+        assert!(info.message().ends_with("bytes truncated)"));
+    }
+
+    #[test]
+    fn test_non_database_error_is_unchanged() {
+        let error = with_query_context(Error::NotFound, "SELECT 1".to_string());
+        assert!(matches!(error, Error::NotFound));
+    }
+}
@@ -62,6 +62,23 @@ pub fn standardize_address(handle: &str) -> String {
     }
 }
 
+/// Like standardize_address, but fails unless the input is 1 to 64 hex characters with an
+/// optional 0x prefix. Use it for addresses parsed from untrusted data.
+pub fn try_standardize_address(handle: &str) -> anyhow::Result<String> {
+    let address = handle.strip_prefix("0x").unwrap_or(handle);
+    anyhow::ensure!(
+        !address.is_empty() && address.len() <= 64,
+        "Invalid address length: {}",
+        handle
+    );
+    anyhow::ensure!(
+        address.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid hex in address: {}",
+        handle
+    );
+    Ok(standardize_address(address))
+}
+
 pub fn hash_str(val: &str) -> String {
     hex::encode(sha2::Sha256::digest(val.as_bytes()))
 }
@@ -498,6 +515,31 @@ mod tests {
         pub default_properties: serde_json::Value,
     }
 
+    #[test]
+    fn test_try_standardize_address() {
+        assert_eq!(
+            try_standardize_address("0x1").unwrap(),
+            standardize_address("0x1")
+        );
+        assert_eq!(
+            try_standardize_address("a").unwrap(),
+            format!("0x{:0>64}", "a")
+        );
+        let full_address = format!("0x{}", "ab".repeat(32));
+        assert_eq!(
+            try_standardize_address(&full_address).unwrap(),
+            full_address
+        );
+
+        // Too short or too long
+        assert!(try_standardize_address("").is_err());
+        assert!(try_standardize_address("0x").is_err());
+        assert!(try_standardize_address(&format!("0x{}", "1".repeat(65))).is_err());
+        // Not hex
+        assert!(try_standardize_address("0xg1").is_err());
+        assert!(try_standardize_address("0x1::coin::CoinStore").is_err());
+    }
+
     #[test]
     fn test_parse_timestamp() {
         let ts = parse_timestamp(
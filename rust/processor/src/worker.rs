@@ -28,7 +28,10 @@ use crate::{
             SINGLE_BATCH_DB_INSERTION_TIME_IN_SECS, SINGLE_BATCH_PARSING_TIME_IN_SECS,
             SINGLE_BATCH_PROCESSING_TIME_IN_SECS, TRANSACTION_UNIX_TIMESTAMP,
         },
-        database::{execute_with_better_error_conn, new_db_pool, run_pending_migrations, PgDbPool},
+        database::{
+            check_no_pending_migrations, execute_with_better_error_conn, new_db_pool,
+            run_pending_migrations, DbWriteConfig, PgDbPool,
+        },
         util::{time_diff_since_pb_timestamp_in_secs, timestamp_to_iso, timestamp_to_unixtime},
     },
 };
@@ -62,6 +65,8 @@ pub struct Worker {
     pub enable_verbose_logging: Option<bool>,
     pub transaction_filter: TransactionFilter,
     pub grpc_response_item_timeout_in_secs: u64,
+    pub db_write_config: DbWriteConfig,
+    pub expected_chain_id: Option<u64>,
 }
 
 impl Worker {
@@ -83,6 +88,9 @@ impl Worker {
         enable_verbose_logging: Option<bool>,
         transaction_filter: TransactionFilter,
         grpc_response_item_timeout_in_secs: u64,
+        dry_run: bool,
+        db_write_retries: u32,
+        expected_chain_id: Option<u64>,
     ) -> Result<Self> {
         let processor_name = processor_config.name();
         info!(processor_name = processor_name, "[Parser] Kicking off");
@@ -92,6 +100,13 @@ impl Worker {
             service_type = PROCESSOR_SERVICE_TYPE,
             "[Parser] Creating connection pool"
         );
+        if dry_run {
+            info!(
+                processor_name = processor_name,
+                service_type = PROCESSOR_SERVICE_TYPE,
+                "[Parser] Dry run, nothing will be written to the database"
+            );
+        }
         let conn_pool = new_db_pool(&postgres_connection_string, db_pool_size)
             .await
             .context("Failed to create connection pool")?;
@@ -118,6 +133,11 @@ impl Worker {
             enable_verbose_logging,
             transaction_filter,
             grpc_response_item_timeout_in_secs,
+            db_write_config: DbWriteConfig {
+                dry_run,
+                max_retries: db_write_retries,
+            },
+            expected_chain_id,
         })
     }
 
@@ -127,7 +147,7 @@ impl Worker {
     /// 3. Start a loop to consume from the buffer. We will have Y threads to process the transactions in parallel. (Y should be less than X for obvious reasons)
     ///   * Note that the batches will be sequential so we won't have problems with gaps
     /// 4. We will keep track of the last processed version and monitoring things like TPS
-    pub async fn run(&mut self) {
+    pub async fn run(&mut self) -> Result<()> {
         let processor_name = self.processor_config.name();
         info!(
             processor_name = processor_name,
@@ -143,6 +163,18 @@ impl Worker {
             "[Parser] Finished migrations"
         );
 
+        let processor = build_processor(
+            &self.processor_config,
+            self.per_table_chunk_sizes.clone(),
+            self.db_pool.clone(),
+            self.db_write_config,
+        )
+        .context("Invalid processor config")?;
+        processor
+            .check_table_field_counts()
+            .await
+            .expect("[Parser] Models don't match the database schema");
+
         let starting_version_from_db = self
             .get_start_version()
             .await
@@ -180,6 +212,7 @@ impl Worker {
             processor_name.to_string(),
         )
         .await;
+        check_expected_chain_id(self.expected_chain_id, chain_id).unwrap();
         self.check_or_update_chain_id(chain_id as i64)
             .await
             .unwrap();
@@ -235,11 +268,6 @@ impl Worker {
         let (gap_detector_sender, gap_detector_receiver) =
             kanal::bounded_async::<ProcessingResult>(BUFFER_SIZE);
         let gap_detection_batch_size = self.gap_detection_batch_size;
-        let processor = build_processor(
-            &self.processor_config,
-            self.per_table_chunk_sizes.clone(),
-            self.db_pool.clone(),
-        );
         tokio::spawn(async move {
             crate::gap_detector::create_gap_detector_status_tracker_loop(
                 gap_detector_receiver,
@@ -269,7 +297,7 @@ impl Worker {
         for task_index in 0..concurrent_tasks {
             let join_handle = self
                 .launch_processor_task(task_index, receiver.clone(), gap_detector_sender.clone())
-                .await;
+                .await?;
             processor_tasks.push(join_handle);
         }
 
@@ -285,6 +313,7 @@ impl Worker {
         futures::future::try_join_all(processor_tasks)
             .await
             .expect("[Processor] Processor tasks have died");
+        Ok(())
     }
 
     async fn launch_processor_task(
@@ -292,7 +321,7 @@ impl Worker {
         task_index: usize,
         receiver: kanal::AsyncReceiver<TransactionsPBResponse>,
         gap_detector_sender: kanal::AsyncSender<ProcessingResult>,
-    ) -> JoinHandle<()> {
+    ) -> Result<JoinHandle<()>> {
         let processor_name = self.processor_config.name();
         let stream_address = self.indexer_grpc_data_service_address.to_string();
         let receiver_clone = receiver.clone();
@@ -303,14 +332,16 @@ impl Worker {
             &self.processor_config,
             self.per_table_chunk_sizes.clone(),
             self.db_pool.clone(),
-        );
+            self.db_write_config,
+        )
+        .context("Invalid processor config")?;
 
         let concurrent_tasks = self.number_concurrent_processing_tasks;
 
         let chain_id = self
             .grpc_chain_id
             .expect("GRPC chain ID has not been fetched yet!");
-        tokio::spawn(async move {
+        Ok(tokio::spawn(async move {
             let task_index_str = task_index.to_string();
             let step = ProcessorStep::ProcessedBatch.get_step();
             let label = ProcessorStep::ProcessedBatch.get_label();
@@ -517,7 +548,7 @@ impl Worker {
                     },
                 }
             }
-        })
+        }))
     }
 
     // For the normal processor build we just use standard Diesel with the postgres
@@ -531,7 +562,11 @@ impl Worker {
         info!("Running migrations: {:?}", self.postgres_connection_string);
         let mut conn =
             PgConnection::establish(&self.postgres_connection_string).expect("migrations failed!");
-        run_pending_migrations(&mut conn);
+        if self.db_write_config.dry_run {
+            check_no_pending_migrations(&mut conn);
+        } else {
+            run_pending_migrations(&mut conn);
+        }
     }
 
     // If the libpq feature isn't enabled, we use diesel async instead. This is used by
@@ -548,13 +583,18 @@ impl Worker {
             .dedicated_connection()
             .await
             .expect("[Parser] Failed to get connection");
+        let dry_run = self.db_write_config.dry_run;
         // We use spawn_blocking since run_pending_migrations is a blocking function.
         tokio::task::spawn_blocking(move || {
             // This lets us use the connection like a normal diesel connection. See more:
             // https://docs.rs/diesel-async/latest/diesel_async/async_connection_wrapper/type.AsyncConnectionWrapper.html
             let mut conn: AsyncConnectionWrapper<diesel_async::AsyncPgConnection> =
                 AsyncConnectionWrapper::from(conn);
-            run_pending_migrations(&mut conn);
+            if dry_run {
+                check_no_pending_migrations(&mut conn);
+            } else {
+                run_pending_migrations(&mut conn);
+            }
         })
         .await
         .expect("[Parser] Failed to run migrations");
@@ -593,6 +633,14 @@ impl Worker {
                 );
                 Ok(chain_id as u64)
             },
+            None if self.db_write_config.dry_run => {
+                info!(
+                    processor_name = processor_name,
+                    chain_id = grpc_chain_id,
+                    "[Parser] Dry run, not adding chain id to db, continue to index..."
+                );
+                Ok(grpc_chain_id as u64)
+            },
             None => {
                 info!(
                     processor_name = processor_name,
@@ -616,6 +664,20 @@ impl Worker {
     }
 }
 
+/// Verify the chain id from GRPC against the one from the config, if any. Unlike the check
+/// against the database, this also catches the wrong network on a fresh database.
+pub fn check_expected_chain_id(expected_chain_id: Option<u64>, grpc_chain_id: u64) -> Result<()> {
+    if let Some(expected_chain_id) = expected_chain_id {
+        anyhow::ensure!(
+            expected_chain_id == grpc_chain_id,
+            "[Parser] Wrong chain detected! Expected chain {} from the config but the stream is for chain {}",
+            expected_chain_id,
+            grpc_chain_id
+        );
+    }
+    Ok(())
+}
+
 async fn fetch_transactions(
     processor_name: &str,
     stream_address: &str,
@@ -707,6 +769,13 @@ pub async fn do_processor(
             .set(time_diff_since_pb_timestamp_in_secs(t));
     }
 
+    // Nothing was written, so don't report the time spent skipping the writes
+    if processor.db_write_config().dry_run {
+        return processed_result.map(|result| ProcessingResult {
+            db_insertion_duration_in_secs: 0.0,
+            ..result
+        });
+    }
     processed_result
 }
 
@@ -719,57 +788,108 @@ pub fn build_processor(
     config: &ProcessorConfig,
     per_table_chunk_sizes: AHashMap<String, usize>,
     db_pool: PgDbPool,
-) -> Processor {
-    match config {
+    db_write_config: DbWriteConfig,
+) -> Result<Processor> {
+    Ok(match config {
         ProcessorConfig::AccountTransactionsProcessor => Processor::from(
-            AccountTransactionsProcessor::new(db_pool, per_table_chunk_sizes),
+            AccountTransactionsProcessor::new(db_pool, per_table_chunk_sizes, db_write_config),
         ),
         ProcessorConfig::AnsProcessor(config) => Processor::from(AnsProcessor::new(
             db_pool,
             config.clone(),
             per_table_chunk_sizes,
+            db_write_config,
         )),
-        ProcessorConfig::CoinProcessor => {
-            Processor::from(CoinProcessor::new(db_pool, per_table_chunk_sizes))
-        },
-        ProcessorConfig::DefaultProcessor => {
-            Processor::from(DefaultProcessor::new(db_pool, per_table_chunk_sizes))
-        },
-        ProcessorConfig::EventsProcessor => {
-            Processor::from(EventsProcessor::new(db_pool, per_table_chunk_sizes))
-        },
-        ProcessorConfig::FungibleAssetProcessor => {
-            Processor::from(FungibleAssetProcessor::new(db_pool, per_table_chunk_sizes))
-        },
-        ProcessorConfig::MonitoringProcessor => Processor::from(MonitoringProcessor::new(db_pool)),
-        ProcessorConfig::NftMetadataProcessor(config) => {
-            Processor::from(NftMetadataProcessor::new(db_pool, config.clone()))
+        ProcessorConfig::CoinProcessor => Processor::from(CoinProcessor::new(
+            db_pool,
+            per_table_chunk_sizes,
+            db_write_config,
+        )),
+        ProcessorConfig::DefaultProcessor => Processor::from(DefaultProcessor::new(
+            db_pool,
+            per_table_chunk_sizes,
+            db_write_config,
+        )),
+        ProcessorConfig::EventsProcessor(config) => Processor::from(EventsProcessor::new(
+            db_pool,
+            config.clone(),
+            per_table_chunk_sizes,
+            db_write_config,
+        )?),
+        ProcessorConfig::FungibleAssetProcessor => Processor::from(FungibleAssetProcessor::new(
+            db_pool,
+            per_table_chunk_sizes,
+            db_write_config,
+        )),
+        ProcessorConfig::MonitoringProcessor => {
+            Processor::from(MonitoringProcessor::new(db_pool, db_write_config))
         },
+        ProcessorConfig::NftMetadataProcessor(config) => Processor::from(
+            NftMetadataProcessor::new(db_pool, config.clone(), db_write_config),
+        ),
         ProcessorConfig::ObjectsProcessor(config) => Processor::from(ObjectsProcessor::new(
             db_pool,
             config.clone(),
             per_table_chunk_sizes,
+            db_write_config,
         )),
         ProcessorConfig::StakeProcessor(config) => Processor::from(StakeProcessor::new(
             db_pool,
             config.clone(),
             per_table_chunk_sizes,
+            db_write_config,
         )),
         ProcessorConfig::TokenProcessor(config) => Processor::from(TokenProcessor::new(
             db_pool,
             config.clone(),
             per_table_chunk_sizes,
+            db_write_config,
         )),
         ProcessorConfig::TokenV2Processor(config) => Processor::from(TokenV2Processor::new(
             db_pool,
             config.clone(),
             per_table_chunk_sizes,
+            db_write_config,
         )),
         ProcessorConfig::TransactionMetadataProcessor => Processor::from(
-            TransactionMetadataProcessor::new(db_pool, per_table_chunk_sizes),
+            TransactionMetadataProcessor::new(db_pool, per_table_chunk_sizes, db_write_config),
         ),
         ProcessorConfig::UserTransactionProcessor => Processor::from(
-            UserTransactionProcessor::new(db_pool, per_table_chunk_sizes),
+            UserTransactionProcessor::new(db_pool, per_table_chunk_sizes, db_write_config),
         ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_expected_chain_id() {
+        assert!(check_expected_chain_id(None, 1).is_ok());
+        assert!(check_expected_chain_id(Some(1), 1).is_ok());
+        assert!(check_expected_chain_id(Some(1), 2).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_build_processor_rejects_invalid_config() {
+        use crate::utils::database::MyDbConnection;
+        use diesel_async::pooled_connection::{bb8::Pool, AsyncDieselConnectionManager};
+
+        // Building a processor doesn't connect, so nothing needs to listen on this port
+        let manager = AsyncDieselConnectionManager::<MyDbConnection>::new(
+            "postgres://postgres@127.0.0.1:1/postgres",
+        );
+        let db_pool = std::sync::Arc::new(Pool::builder().build_unchecked(manager));
+        let config: ProcessorConfig = serde_json::from_value(serde_json::json!({
+            "type": "events_processor",
+            "event_filters": ["("],
+            "event_filter_mode": "regex",
+        }))
+        .unwrap();
+
+        assert!(
+            build_processor(&config, AHashMap::new(), db_pool, DbWriteConfig::default()).is_err()
+        );
     }
 }